@@ -0,0 +1,183 @@
+use crate::isentropic::IsentropicFlowError;
+use crate::error::CompressibleFlowError;
+use crate::gas::{self, AIR};
+use crate::pitot;
+
+
+// international standard atmosphere sea level values, SI units, with the
+// dry air properties taken from gas::AIR
+pub const SEA_LEVEL_TEMPERATURE: f64 = 288.15;                              // K
+pub const SEA_LEVEL_PRESSURE: f64 = 101325.0;                               // Pa
+pub const SEA_LEVEL_DENSITY: f64 = 1.225;                                   // kg/m³
+pub const GAS_CONSTANT: f64 = gas::UNIVERSAL_GAS_CONSTANT / AIR.molar_mass; // J/(kg K)
+pub const SPECIFIC_HEAT_RATIO: f64 = AIR.specific_heat_ratio;
+const GRAVITY: f64 = 9.80665;                                               // m/s²
+
+// (base geopotential altitude m, lapse rate K/m) from 2 km below sea level,
+// where the first layer is extended down, up to 32 km
+const ATMOSPHERE_LAYERS: [(f64, f64); 3] = [(0.0, -0.0065), (11000.0, 0.0), (20000.0, 0.001)];
+const ATMOSPHERE_FLOOR: f64 = -2000.0;
+const ATMOSPHERE_CEILING: f64 = 32000.0;
+
+#[derive(Debug)]
+pub struct StandardAtmosphere {
+    pub altitude: f64,       // geopotential altitude, m
+    pub temperature: f64,    // K
+    pub pressure: f64,       // Pa
+    pub density: f64,        // kg/m³
+    pub speed_of_sound: f64, // m/s
+}
+
+#[derive(Debug)]
+pub struct AirData {
+    pub mach_number: f64,
+    pub calibrated_airspeed: f64, // m/s
+    pub equivalent_airspeed: f64, // m/s
+    pub true_airspeed: f64,       // m/s
+    pub static_temperature: f64,  // K
+    pub pressure_altitude: f64,   // m
+}
+
+impl StandardAtmosphere {
+    pub fn from_altitude(altitude: f64) -> Result<StandardAtmosphere, CompressibleFlowError> {
+        if !(ATMOSPHERE_FLOOR..=ATMOSPHERE_CEILING).contains(&altitude) {
+            return Err(CompressibleFlowError::InvalidAltitude);
+        }
+        let (mut temperature, mut pressure) = (SEA_LEVEL_TEMPERATURE, SEA_LEVEL_PRESSURE);
+        for (i, &(base, lapse_rate)) in ATMOSPHERE_LAYERS.iter().enumerate() {
+            let top: f64 = ATMOSPHERE_LAYERS.get(i + 1).map_or(ATMOSPHERE_CEILING, |layer| layer.0);
+            let (next_temperature, next_pressure) = calc_layer(temperature, pressure, lapse_rate, altitude.min(top) - base);
+            temperature = next_temperature;
+            pressure = next_pressure;
+            if altitude <= top {
+                break;
+            }
+        }
+        Ok(StandardAtmosphere {
+            altitude,
+            temperature,
+            pressure,
+            density: pressure / (GAS_CONSTANT * temperature),
+            speed_of_sound: calc_speed_of_sound(temperature),
+        })
+    }
+}
+
+impl AirData {
+    pub fn from_pitot_static(impact_pressure: f64, static_pressure: f64, total_air_temperature: f64, recovery_factor: Option<f64>) -> Result<AirData, CompressibleFlowError> {
+        // impact pressure qc = pt - ps from the pitot probe, static pressure from
+        // the static ports and the total air temperature probe reading
+        if total_air_temperature <= 0.0 {
+            return Err(IsentropicFlowError::InvalidTemperatureRatio.into());
+        }
+        let mach_number: f64 = calc_mach_from_impact_pressure(impact_pressure, static_pressure)?;
+        let static_temperature: f64 = calc_static_temperature(total_air_temperature, mach_number, recovery_factor)?;
+
+        Ok(AirData {
+            mach_number,
+            calibrated_airspeed: calc_calibrated_airspeed(impact_pressure)?,
+            equivalent_airspeed: calc_equivalent_airspeed(mach_number, static_pressure)?,
+            true_airspeed: mach_number * calc_speed_of_sound(static_temperature),
+            static_temperature,
+            pressure_altitude: calc_pressure_altitude(static_pressure)?,
+        })
+    }
+}
+
+pub fn calc_speed_of_sound(temperature: f64) -> f64 {
+    (SPECIFIC_HEAT_RATIO * GAS_CONSTANT * temperature).sqrt()
+}
+
+pub fn calc_pressure_altitude(static_pressure: f64) -> Result<f64, CompressibleFlowError> {
+    // standard atmosphere altitude with the given static pressure, above the
+    // sea level pressure this is negative down to the floor of the atmosphere
+    let mut temperature: f64 = SEA_LEVEL_TEMPERATURE;
+    let mut pressure: f64 = SEA_LEVEL_PRESSURE;
+    if static_pressure <= 0.0 {
+        return Err(IsentropicFlowError::InvalidPressureRatio.into());
+    }
+    if static_pressure > calc_layer(temperature, pressure, ATMOSPHERE_LAYERS[0].1, ATMOSPHERE_FLOOR).1 {
+        return Err(CompressibleFlowError::InvalidAltitude);
+    }
+    for (i, &(base, lapse_rate)) in ATMOSPHERE_LAYERS.iter().enumerate() {
+        let top: f64 = ATMOSPHERE_LAYERS.get(i + 1).map_or(ATMOSPHERE_CEILING, |layer| layer.0);
+        let (top_temperature, top_pressure) = calc_layer(temperature, pressure, lapse_rate, top - base);
+        if static_pressure >= top_pressure {
+            let height: f64 = if lapse_rate == 0.0 {
+                -GAS_CONSTANT * temperature / GRAVITY * (static_pressure / pressure).ln()
+            } else {
+                temperature / lapse_rate * ((static_pressure / pressure).powf(-lapse_rate * GAS_CONSTANT / GRAVITY) - 1.0)
+            };
+            return Ok(base + height);
+        }
+        temperature = top_temperature;
+        pressure = top_pressure;
+    }
+    Err(CompressibleFlowError::InvalidAltitude)
+}
+
+pub fn calc_mach_from_impact_pressure(impact_pressure: f64, static_pressure: f64) -> Result<f64, CompressibleFlowError> {
+    // subsonic and supersonic, the pitot reads behind a normal shock above mach 1
+    if static_pressure <= 0.0 || impact_pressure < 0.0 {
+        return Err(IsentropicFlowError::InvalidPressureRatio.into());
+    }
+    pitot::calc_mach_from_pitot_pressure_ratio(impact_pressure / static_pressure + 1.0, SPECIFIC_HEAT_RATIO)
+}
+
+pub fn calc_impact_pressure_from_mach(mach_number: f64, static_pressure: f64) -> Result<f64, CompressibleFlowError> {
+    Ok(static_pressure * (pitot::calc_pitot_pressure_ratio_from_mach(mach_number, SPECIFIC_HEAT_RATIO)? - 1.0))
+}
+
+pub fn calc_calibrated_airspeed(impact_pressure: f64) -> Result<f64, CompressibleFlowError> {
+    // the speed at standard sea level that gives the same impact pressure
+    let sea_level_mach: f64 = calc_mach_from_impact_pressure(impact_pressure, SEA_LEVEL_PRESSURE)?;
+    Ok(sea_level_mach * calc_speed_of_sound(SEA_LEVEL_TEMPERATURE))
+}
+
+pub fn calc_impact_pressure_from_calibrated_airspeed(calibrated_airspeed: f64) -> Result<f64, CompressibleFlowError> {
+    calc_impact_pressure_from_mach(calibrated_airspeed / calc_speed_of_sound(SEA_LEVEL_TEMPERATURE), SEA_LEVEL_PRESSURE)
+}
+
+pub fn calc_equivalent_airspeed(mach_number: f64, static_pressure: f64) -> Result<f64, CompressibleFlowError> {
+    // EAS = TAS √(ρ / ρSL) = M aSL √(p / pSL)
+    if static_pressure <= 0.0 {
+        return Err(IsentropicFlowError::InvalidPressureRatio.into());
+    }
+    if mach_number < 0.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    Ok(mach_number * calc_speed_of_sound(SEA_LEVEL_TEMPERATURE) * (static_pressure / SEA_LEVEL_PRESSURE).sqrt())
+}
+
+pub fn calc_true_airspeed_from_calibrated(calibrated_airspeed: f64, static_pressure: f64, static_temperature: f64) -> Result<f64, CompressibleFlowError> {
+    let impact_pressure: f64 = calc_impact_pressure_from_calibrated_airspeed(calibrated_airspeed)?;
+    let mach_number: f64 = calc_mach_from_impact_pressure(impact_pressure, static_pressure)?;
+    Ok(mach_number * calc_speed_of_sound(static_temperature))
+}
+
+pub fn calc_calibrated_from_true_airspeed(true_airspeed: f64, static_pressure: f64, static_temperature: f64) -> Result<f64, CompressibleFlowError> {
+    let mach_number: f64 = true_airspeed / calc_speed_of_sound(static_temperature);
+    calc_calibrated_airspeed(calc_impact_pressure_from_mach(mach_number, static_pressure)?)
+}
+
+pub fn calc_total_air_temperature(static_temperature: f64, mach_number: f64, recovery_factor: Option<f64>) -> Result<f64, CompressibleFlowError> {
+    // TAT = T (1 + r (γ - 1) M² / 2), r = 1 by default for an ideal probe
+    let recovery_factor: f64 = recovery_factor.unwrap_or(1.0);
+    if !(0.0..=1.0).contains(&recovery_factor) {
+        return Err(IsentropicFlowError::InvalidTemperatureRatio.into());
+    }
+    Ok(static_temperature * (1.0 + recovery_factor * (SPECIFIC_HEAT_RATIO - 1.0) / 2.0 * mach_number.powi(2)))
+}
+
+pub fn calc_static_temperature(total_air_temperature: f64, mach_number: f64, recovery_factor: Option<f64>) -> Result<f64, CompressibleFlowError> {
+    Ok(total_air_temperature / calc_total_air_temperature(1.0, mach_number, recovery_factor)?)
+}
+
+fn calc_layer(base_temperature: f64, base_pressure: f64, lapse_rate: f64, height: f64) -> (f64, f64) {
+    // temperature and pressure a height above the base of a layer
+    if lapse_rate == 0.0 {
+        return (base_temperature, base_pressure * (-GRAVITY * height / (GAS_CONSTANT * base_temperature)).exp());
+    }
+    let temperature: f64 = base_temperature + lapse_rate * height;
+    (temperature, base_pressure * (temperature / base_temperature).powf(-GRAVITY / (lapse_rate * GAS_CONSTANT)))
+}
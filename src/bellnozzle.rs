@@ -0,0 +1,640 @@
+use std::f64::consts::PI;
+use crate::isentropic::{self, valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::characteristics::{intersect_lines, CharacteristicPoint};
+use crate::numerics::bisection;
+
+
+#[derive(Debug, Clone, Copy)]
+pub enum BellContour {
+    TruncatedIdeal(f64),        // fraction of the ideal (uniform exit) contour length kept
+    OptimumTruncatedIdeal(f64), // shortened to a fraction of the ideal contour length at the full area ratio
+    ThrustOptimisedParabola {   // rao's parabolic approximation of the thrust optimum contour
+        length_fraction: f64,    // fraction of the 15° cone length at the same area ratio
+        initial_wall_angle: f64, // θn, from rao's charts for the area ratio and length fraction
+        exit_wall_angle: f64,    // θe
+    },
+}
+
+#[derive(Debug)]
+pub struct BellNozzle {
+    // lengths are nondimensionalised by the throat radius, the throat is a
+    // sharp corner at (0, 1) with a straight sonic line and the axis along y = 0
+    pub wall_contour: Vec<(f64, f64)>,                 // (x, r) from the throat to the lip
+    pub characteristic_net: Vec<CharacteristicPoint>,  // every node of the analysis net
+    pub exit_characteristic: Vec<CharacteristicPoint>, // C- from the lip to the axis
+    pub initial_wall_angle: f64,                       // θn, wall angle leaving the throat
+    pub exit_wall_angle: f64,                          // θe, wall angle at the lip
+    pub length: f64,                                   // L / r*
+    pub area_ratio: f64,                               // Ae / A*
+    pub thrust_coefficient: f64,                       // vacuum F / (p0 A*)
+    pub divergence_efficiency: f64,                    // λ, axial over total momentum flux
+    pub divergence_loss: f64,                          // 1 - λ
+}
+
+impl BellNozzle {
+    pub fn new(design_exit_mach: f64, contour: BellContour, characteristic_lines: usize, specific_heat_ratio: f64) -> Result<BellNozzle, CompressibleFlowError> {
+        if !valid_specific_heat_ratio(specific_heat_ratio) {
+            return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+        }
+        if design_exit_mach <= 1.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        if characteristic_lines < 3 {
+            return Err(CompressibleFlowError::InvalidNumberOfCharacteristics);
+        }
+
+        let wall: Vec<(f64, f64)> = match contour {
+            BellContour::TruncatedIdeal(length_fraction) => {
+                if length_fraction <= 0.0 || length_fraction > 1.0 {
+                    return Err(CompressibleFlowError::InvalidGeometry);
+                }
+                let ideal_wall = calc_ideal_contour(design_exit_mach, characteristic_lines, specific_heat_ratio)?;
+                truncate_contour(&ideal_wall, length_fraction)
+            }
+            BellContour::OptimumTruncatedIdeal(length_fraction) => {
+                if length_fraction <= 0.0 || length_fraction > 1.0 {
+                    return Err(CompressibleFlowError::InvalidGeometry);
+                }
+                calc_optimum_truncated_contour(design_exit_mach, length_fraction, characteristic_lines, specific_heat_ratio)?
+            }
+            BellContour::ThrustOptimisedParabola { length_fraction, initial_wall_angle, exit_wall_angle } => {
+                let area_ratio: f64 = isentropic::calc_area_ratio_from_mach(design_exit_mach, specific_heat_ratio)?;
+                calc_parabolic_contour(area_ratio, length_fraction, initial_wall_angle, exit_wall_angle, characteristic_lines)?
+            }
+        };
+
+        analyse_contour(&wall, characteristic_lines, specific_heat_ratio)
+    }
+
+    pub fn exit_mach_profile(&self) -> Vec<(f64, f64)> {
+        // (r, M) along the exit characteristic from the lip to the axis
+        self.exit_characteristic.iter().map(|point| (point.y, point.mach_number)).collect()
+    }
+}
+
+pub fn calc_ideal_contour(exit_mach: f64, characteristic_lines: usize, specific_heat_ratio: f64) -> Result<Vec<(f64, f64)>, CompressibleFlowError> {
+    // axisymmetric minimum length contour giving uniform parallel flow at the
+    // exit, the corner fan is sized so the axis reaches the exit mach number where
+    // the last fan characteristic meets it, the turning contour is then the
+    // streamline through the corner in the region between that characteristic
+    // and the straight exit characteristic
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    if exit_mach <= 1.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    let n: usize = characteristic_lines;
+    let exit_prandtl_meyer_angle: f64 = isentropic::prandtl_meyer_function(exit_mach, specific_heat_ratio)?;
+
+    // size the fan, the planar value ν(Me) / 2 is an upper bound
+    let f = |max_wall_angle: f64| {
+        match calc_corner_fan(max_wall_angle, n, specific_heat_ratio) {
+            Ok((_, net)) => net[n - 1][n - 1].mach_number - exit_mach,
+            Err(_) => f64::NAN,
+        }
+    };
+    // but at high mach numbers the reflected fan runs out of prandtl meyer
+    // angle before reaching it, so pull the upper end in until the fan exists
+    let mut upper_angle: f64 = exit_prandtl_meyer_angle / 2.0;
+    let mut f_upper: f64 = f(upper_angle);
+    while f_upper.is_nan() && upper_angle > 1e-3 {
+        upper_angle *= 0.8;
+        f_upper = f(upper_angle);
+    }
+    let f_lower: f64 = f(1e-4);
+    if !f_lower.is_finite() || !f_upper.is_finite() || f_lower * f_upper > 0.0 {
+        return Err(IsentropicFlowError::MathError.into());
+    }
+    let max_wall_angle: f64 = bisection(&f, 1e-4, upper_angle, Some(1e-10), None);
+    let (corner, net) = calc_corner_fan(max_wall_angle, n, specific_heat_ratio)?;
+
+    // last fan characteristic from the axis up to the corner
+    let mut last_fan_line: Vec<CharacteristicPoint> = net[n - 1].iter().rev().cloned().collect();
+    last_fan_line.push(corner[n - 1]);
+    let axis_point: CharacteristicPoint = last_fan_line[0];
+
+    // straight exit characteristic carrying the uniform exit flow, it ends at
+    // the lip where it carries the same mass flow as the discrete fan, using the
+    // isentropic area ratio here instead leaves a kink at the lip
+    let fan_mass_flow: f64 = last_fan_line.windows(2)
+        .map(|pair| calc_segment_mass_flow(&pair[0], &pair[1], specific_heat_ratio))
+        .sum::<Result<f64, _>>()?;
+    let exit_radius: f64 = (fan_mass_flow / (PI * mass_flux(exit_mach, specific_heat_ratio)?)).sqrt();
+    let exit_mach_angle: f64 = isentropic::calc_mach_angle_from_mach(exit_mach)?;
+    let exit_line: Vec<CharacteristicPoint> = (0..=n)
+        .map(|m| {
+            let y = exit_radius * m as f64 / n as f64;
+            CharacteristicPoint::new(axis_point.x + y / exit_mach_angle.tan(), y, 0.0, exit_prandtl_meyer_angle, specific_heat_ratio)
+        })
+        .collect::<Result<_, _>>()?;
+
+    // goursat problem between the two characteristics, region[m][k] lies on the
+    // C- through exit point m and the C+ through fan point k
+    let mut region: Vec<Vec<CharacteristicPoint>> = vec![last_fan_line.clone()];
+    for m in 1..=n {
+        let mut line: Vec<CharacteristicPoint> = vec![exit_line[m]];
+        for k in 1..last_fan_line.len() {
+            let point = calc_axisymmetric_interior_point(&line[k - 1], &region[m - 1][k], specific_heat_ratio)?;
+            line.push(point);
+        }
+        region.push(line);
+    }
+
+    // trace the wall streamline from the corner, starting at the fan angle and
+    // stepping across each C- of the region with the averaged flow angle
+    let mut wall: Vec<(f64, f64)> = vec![(0.0, 1.0)];
+    let mut wall_angle: f64 = max_wall_angle;
+    for (m, line) in region.iter().enumerate().skip(1) {
+        let origin: (f64, f64) = wall[wall.len() - 1];
+        let mut slope: f64 = wall_angle.tan();
+        let mut crossing: (f64, f64, f64) = (line[0].x, line[0].y, line[0].flow_angle);
+        for _ in 0..CORRECTOR_ITERATIONS {
+            crossing = match intersect_characteristic(origin, slope, line) {
+                Some(value) => value,
+                // only the lip can be missed, by round off in the mass flow
+                None if m == n => (line[0].x, line[0].y, line[0].flow_angle),
+                None => return Err(IsentropicFlowError::MathError.into()),
+            };
+            slope = (0.5 * (wall_angle + crossing.2)).tan();
+        }
+        wall.push((crossing.0, crossing.1));
+        wall_angle = crossing.2;
+    }
+
+    Ok(wall)
+}
+
+fn calc_optimum_truncated_contour(exit_mach: f64, length_fraction: f64, characteristic_lines: usize, specific_heat_ratio: f64) -> Result<Vec<(f64, f64)>, CompressibleFlowError> {
+    // shortened contour keeping the full exit area ratio, taken as the ideal
+    // contour of a higher design mach number truncated where it reaches the
+    // exit radius at the required length, this is ahlberg's truncated ideal
+    // approximation and not rao's control surface optimum, so θn and θe are
+    // whatever the truncated contour gives rather than optimised
+    let ideal_wall: Vec<(f64, f64)> = calc_ideal_contour(exit_mach, characteristic_lines, specific_heat_ratio)?;
+    let (ideal_length, exit_radius) = ideal_wall[ideal_wall.len() - 1];
+    if length_fraction == 1.0 {
+        return Ok(ideal_wall);
+    }
+    let length: f64 = length_fraction * ideal_length;
+
+    let f = |design_mach: f64| {
+        match calc_ideal_contour(design_mach, characteristic_lines, specific_heat_ratio) {
+            Ok(wall) if wall[wall.len() - 1].0 > length => interpolate_height(length, &wall) - exit_radius,
+            _ => f64::NAN,
+        }
+    };
+    // the ideal contour itself sits below the exit radius at the shortened
+    // length, double the design mach number until one sits above it, a failed
+    // contour is nan and has to stop the search rather than end it
+    let mut upper_mach: f64 = 2.0 * exit_mach;
+    let mut f_upper: f64 = f(upper_mach);
+    while f_upper < 0.0 {
+        upper_mach *= 2.0;
+        if upper_mach > 64.0 * exit_mach {
+            return Err(CompressibleFlowError::InvalidGeometry);
+        }
+        f_upper = f(upper_mach);
+    }
+    if !f_upper.is_finite() {
+        return Err(CompressibleFlowError::InvalidGeometry);
+    }
+    let design_mach: f64 = bisection(&f, exit_mach, upper_mach, Some(1e-6), None);
+
+    let design_wall: Vec<(f64, f64)> = calc_ideal_contour(design_mach, characteristic_lines, specific_heat_ratio)?;
+    Ok(truncate_contour(&design_wall, length / design_wall[design_wall.len() - 1].0))
+}
+
+pub fn calc_parabolic_contour(area_ratio: f64, length_fraction: f64, initial_wall_angle: f64, exit_wall_angle: f64, characteristic_lines: usize) -> Result<Vec<(f64, f64)>, CompressibleFlowError> {
+    // rao's thrust optimised parabola, a circular arc of 0.382 r* turns the
+    // wall from the throat to θn and a quadratic bezier then takes it to the lip
+    // at θe with its middle point where the two tangents meet, the length is a
+    // fraction of the 15° cone with a 1.5 r* throat radius and the same exit radius
+    // the analysis starts the arc with a corner fan as wide as its first
+    // segment, so the initial wall angle it reports is that rather than θn
+    if area_ratio <= 1.0 || length_fraction <= 0.0 {
+        return Err(CompressibleFlowError::InvalidGeometry);
+    }
+    if !(0.0..initial_wall_angle).contains(&exit_wall_angle) || initial_wall_angle >= PI / 2.0 {
+        return Err(CompressibleFlowError::InvalidGeometry);
+    }
+    let exit_radius: f64 = area_ratio.sqrt();
+    let cone_angle: f64 = 15.0_f64.to_radians();
+    let length: f64 = length_fraction * (exit_radius - 1.0 + 1.5 * (1.0 / cone_angle.cos() - 1.0)) / cone_angle.tan();
+
+    let n: usize = characteristic_lines;
+    let mut wall: Vec<(f64, f64)> = (0..=n)
+        .map(|i| {
+            let angle = initial_wall_angle * i as f64 / n as f64;
+            (THROAT_ARC_RADIUS * angle.sin(), 1.0 + THROAT_ARC_RADIUS * (1.0 - angle.cos()))
+        })
+        .collect();
+    let start: (f64, f64) = wall[n];
+
+    // the tangents have to meet between the end of the arc and the lip or the
+    // parabola turns back on itself
+    let control_x: f64 = (exit_radius - start.1 + initial_wall_angle.tan() * start.0 - exit_wall_angle.tan() * length)
+        / (initial_wall_angle.tan() - exit_wall_angle.tan());
+    if control_x <= start.0 || control_x >= length {
+        return Err(CompressibleFlowError::InvalidGeometry);
+    }
+    let control: (f64, f64) = (control_x, start.1 + initial_wall_angle.tan() * (control_x - start.0));
+
+    let points: usize = 4 * n;
+    wall.extend((1..=points).map(|i| {
+        let t = i as f64 / points as f64;
+        (
+            (1.0 - t).powi(2) * start.0 + 2.0 * (1.0 - t) * t * control.0 + t.powi(2) * length,
+            (1.0 - t).powi(2) * start.1 + 2.0 * (1.0 - t) * t * control.1 + t.powi(2) * exit_radius,
+        )
+    }));
+    Ok(wall)
+}
+
+pub fn analyse_contour(wall: &[(f64, f64)], characteristic_lines: usize, specific_heat_ratio: f64) -> Result<BellNozzle, CompressibleFlowError> {
+    // marches the axisymmetric net through a given wall contour starting with a
+    // centred fan at the throat corner, every marching line is a C- running from
+    // the wall to the axis and the last one leaves the lip
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    if wall.len() < 2 || wall.windows(2).any(|pair| pair[1].0 <= pair[0].0) {
+        return Err(CompressibleFlowError::InvalidGeometry);
+    }
+    let n: usize = characteristic_lines;
+    let wall_angles: Vec<f64> = calc_wall_angles(wall);
+    let initial_wall_angle: f64 = wall_angles[0];
+    let exit_wall_angle: f64 = wall_angles[wall.len() - 1];
+    let lip: (f64, f64) = wall[wall.len() - 1];
+    if initial_wall_angle <= 0.0 {
+        return Err(CompressibleFlowError::InvalidGeometry);
+    }
+
+    let (corner, net) = calc_corner_fan(initial_wall_angle, n, specific_heat_ratio)?;
+    let mut characteristic_net: Vec<CharacteristicPoint> = net.iter().flatten().cloned().collect();
+    let mut line: Vec<CharacteristicPoint> = vec![corner[n - 1]];
+    line.extend(net[n - 1].iter().cloned());
+
+    // wall points are normally where the C+ of the previous line reach the wall,
+    // where those are too sparse for the wall length or turning between them
+    // (next to the throat the fan only sends a few C+ there) an inverse wall
+    // point is inserted so the wall curvature is resolved
+    let max_wall_step: f64 = lip.0 / n as f64;
+    let max_wall_turn: f64 = wall_angles.iter().fold(0.0, |max: f64, angle| max.max(angle.abs())) / n as f64;
+    let mut marched_lines: usize = 0;
+    let exit_characteristic: Vec<CharacteristicPoint> = loop {
+        marched_lines += 1;
+        if marched_lines > MAX_MARCHING_LINES {
+            return Err(IsentropicFlowError::MathError.into());
+        }
+        let target_x: f64 = wall.iter().zip(wall_angles.iter())
+            .find(|(point, angle)| point.0 > line[0].x && (**angle - line[0].flow_angle).abs() > max_wall_turn)
+            .map_or(f64::INFINITY, |(point, _)| point.0)
+            .min(line[0].x + max_wall_step);
+        match calc_axisymmetric_wall_point(&line[1], wall, &wall_angles, specific_heat_ratio)? {
+            Some(wall_point) if wall_point.x <= target_x => {
+                let next_line: Vec<CharacteristicPoint> = march_minus_characteristic(wall_point, &line[2..], specific_heat_ratio)?;
+                characteristic_net.extend(next_line.iter().cloned());
+                line = next_line;
+            }
+            _ if target_x < lip.0 => {
+                let (x, y, flow_angle) = interpolate_wall(target_x, wall, &wall_angles);
+                let wall_point = calc_axisymmetric_inverse_wall_point(&line, (x, y), flow_angle, specific_heat_ratio)?;
+                let next_line: Vec<CharacteristicPoint> = march_minus_characteristic(wall_point, &line[1..], specific_heat_ratio)?;
+                characteristic_net.extend(next_line.iter().cloned());
+                line = next_line;
+            }
+            _ => {
+                // the next wall point would be past the lip, so the last C- is
+                // started from the lip itself with an inverse wall point
+                let lip_point = calc_axisymmetric_inverse_wall_point(&line, lip, exit_wall_angle, specific_heat_ratio)?;
+                let lip_line: Vec<CharacteristicPoint> = march_minus_characteristic(lip_point, &line[1..], specific_heat_ratio)?;
+                characteristic_net.extend(lip_line.iter().cloned());
+                break lip_line;
+            }
+        }
+    };
+
+    // integrate mass, axial momentum and pressure across the exit characteristic
+    let gamma: f64 = specific_heat_ratio;
+    let mut momentum: f64 = 0.0;
+    let mut axial_momentum: f64 = 0.0;
+    let mut pressure_force: f64 = 0.0;
+    for pair in exit_characteristic.windows(2).rev() {
+        let (upper, lower) = (&pair[0], &pair[1]);
+        let segment_mass_flow: f64 = calc_segment_mass_flow(lower, upper, gamma)?;
+        let mach_number: f64 = 0.5 * (upper.mach_number + lower.mach_number);
+        let flow_angle: f64 = 0.5 * (upper.flow_angle + lower.flow_angle);
+        let velocity: f64 = mach_number * isentropic::calc_temperature_ratio_from_mach(mach_number, gamma)?.sqrt();
+        let pressure: f64 = isentropic::calc_pressure_ratio_from_mach(mach_number, gamma)?;
+        momentum += segment_mass_flow * velocity;
+        axial_momentum += segment_mass_flow * velocity * flow_angle.cos();
+        pressure_force += pressure * PI * (upper.y.powi(2) - lower.y.powi(2));
+    }
+    let divergence_efficiency: f64 = axial_momentum / momentum;
+
+    Ok(BellNozzle {
+        wall_contour: wall.to_vec(),
+        characteristic_net,
+        exit_characteristic,
+        initial_wall_angle,
+        exit_wall_angle,
+        length: lip.0,
+        area_ratio: lip.1.powi(2),
+        thrust_coefficient: (gamma * axial_momentum + pressure_force) / PI,
+        divergence_efficiency,
+        divergence_loss: 1.0 - divergence_efficiency,
+    })
+}
+
+fn march_minus_characteristic(start: CharacteristicPoint, plus_points: &[CharacteristicPoint], specific_heat_ratio: f64) -> Result<Vec<CharacteristicPoint>, CompressibleFlowError> {
+    // C- from start across the C+ through each of plus_points down to the axis,
+    // C+ that the C- would only cross below the axis are dropped, as are C+ it
+    // would cross behind its last point which happens when the previous C- ran
+    // almost on top of this one
+    let mut line: Vec<CharacteristicPoint> = vec![start];
+    for plus_point in plus_points {
+        let last: CharacteristicPoint = line[line.len() - 1];
+        let point: CharacteristicPoint = calc_axisymmetric_interior_point(&last, plus_point, specific_heat_ratio)?;
+        if point.y <= 0.0 {
+            break;
+        }
+        if point.y < last.y && point.x > last.x {
+            line.push(point);
+        }
+    }
+    let axis_point = calc_axisymmetric_symmetry_point(&line[line.len() - 1], specific_heat_ratio)?;
+    line.push(axis_point);
+    Ok(line)
+}
+
+pub fn calc_axisymmetric_interior_point(minus_point: &CharacteristicPoint, plus_point: &CharacteristicPoint, specific_heat_ratio: f64) -> Result<CharacteristicPoint, CompressibleFlowError> {
+    // along C-: d(ν + θ) = sinθ sinμ / (y cos(θ - μ)) dx
+    // along C+: d(ν - θ) = sinθ sinμ / (y cos(θ + μ)) dx
+    // solved with a predictor and repeated corrector steps
+    let mut point: CharacteristicPoint = CharacteristicPoint::new(
+        minus_point.x,
+        minus_point.y,
+        0.5 * (minus_point.minus_invariant() + plus_point.plus_invariant()),
+        0.5 * (minus_point.minus_invariant() - plus_point.plus_invariant()),
+        specific_heat_ratio,
+    )?;
+    let mut predictor: bool = true;
+
+    for _ in 0..CORRECTOR_ITERATIONS {
+        let partner: CharacteristicPoint = if predictor { *minus_point } else { point };
+        let minus_slope: f64 = (0.5 * ((minus_point.flow_angle - minus_point.mach_angle) + (partner.flow_angle - partner.mach_angle))).tan();
+        let partner: CharacteristicPoint = if predictor { *plus_point } else { point };
+        let plus_slope: f64 = (0.5 * ((plus_point.flow_angle + plus_point.mach_angle) + (partner.flow_angle + partner.mach_angle))).tan();
+        let (x, y) = intersect_lines((minus_point.x, minus_point.y), minus_slope, (plus_point.x, plus_point.y), plus_slope)?;
+        point.x = x;
+        point.y = y;
+
+        let partner: Option<&CharacteristicPoint> = if predictor { None } else { Some(&point) };
+        let minus_riemann: f64 = minus_point.minus_invariant()
+            + source_increment(minus_point, partner, false, x - minus_point.x, y - minus_point.y);  // ν + θ
+        let plus_riemann: f64 = -plus_point.plus_invariant()
+            + source_increment(plus_point, partner, true, x - plus_point.x, y - plus_point.y);     // ν - θ
+        point = CharacteristicPoint::new(x, y, 0.5 * (minus_riemann - plus_riemann), 0.5 * (minus_riemann + plus_riemann), specific_heat_ratio)?;
+        predictor = false;
+    }
+    Ok(point)
+}
+
+pub fn calc_axisymmetric_symmetry_point(minus_point: &CharacteristicPoint, specific_heat_ratio: f64) -> Result<CharacteristicPoint, CompressibleFlowError> {
+    // θ = 0 on the axis, the source term there is singular so only the
+    // upstream value is used
+    let mut point: CharacteristicPoint = CharacteristicPoint::new(minus_point.x, 0.0, 0.0, minus_point.minus_invariant(), specific_heat_ratio)?;
+    let mut predictor: bool = true;
+
+    for _ in 0..CORRECTOR_ITERATIONS {
+        let partner: CharacteristicPoint = if predictor { *minus_point } else { point };
+        let minus_slope: f64 = (0.5 * ((minus_point.flow_angle - minus_point.mach_angle) + (partner.flow_angle - partner.mach_angle))).tan();
+        if minus_slope >= 0.0 {
+            return Err(IsentropicFlowError::MathError.into());
+        }
+        let x: f64 = minus_point.x - minus_point.y / minus_slope;
+        let partner: Option<&CharacteristicPoint> = if predictor { None } else { Some(&point) };
+        let prandtl_meyer_angle: f64 = minus_point.minus_invariant()
+            + source_increment(minus_point, partner, false, x - minus_point.x, -minus_point.y);
+        point = CharacteristicPoint::new(x, 0.0, 0.0, prandtl_meyer_angle, specific_heat_ratio)?;
+        predictor = false;
+    }
+    Ok(point)
+}
+
+pub fn calc_axisymmetric_wall_point(plus_point: &CharacteristicPoint, wall: &[(f64, f64)], wall_angles: &[f64], specific_heat_ratio: f64) -> Result<Option<CharacteristicPoint>, CompressibleFlowError> {
+    // direct wall point, the C+ through plus_point is run up to the wall and the
+    // wall angle fixes θ, returns none if the C+ misses the end of the wall
+    let mut slope: f64 = (plus_point.flow_angle + plus_point.mach_angle).tan();
+    let mut point: Option<CharacteristicPoint> = None;
+
+    for _ in 0..CORRECTOR_ITERATIONS {
+        let (x, y, flow_angle) = match intersect_wall((plus_point.x, plus_point.y), slope, wall, wall_angles) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        // the previous estimate supplies the mach angle at the wall for the corrector
+        let partner: Option<CharacteristicPoint> = point.map(|previous| CharacteristicPoint { x, y, flow_angle, ..previous });
+        let prandtl_meyer_angle: f64 = -plus_point.plus_invariant() + flow_angle
+            + source_increment(plus_point, partner.as_ref(), true, x - plus_point.x, y - plus_point.y);
+        let new_point = CharacteristicPoint::new(x, y, flow_angle, prandtl_meyer_angle, specific_heat_ratio)?;
+        slope = (0.5 * ((plus_point.flow_angle + plus_point.mach_angle) + (new_point.flow_angle + new_point.mach_angle))).tan();
+        point = Some(new_point);
+    }
+    Ok(point)
+}
+
+fn calc_axisymmetric_inverse_wall_point(line: &[CharacteristicPoint], wall_position: (f64, f64), wall_angle: f64, specific_heat_ratio: f64) -> Result<CharacteristicPoint, CompressibleFlowError> {
+    // inverse wall point, finds where on the first segment of the previous line
+    // the C+ through a given wall position starts
+    let (wall_point, interior_point) = (&line[0], &line[1]);
+    let interpolate = |s: f64| -> Result<CharacteristicPoint, CompressibleFlowError> {
+        CharacteristicPoint::new(
+            wall_point.x + s * (interior_point.x - wall_point.x),
+            wall_point.y + s * (interior_point.y - wall_point.y),
+            wall_point.flow_angle + s * (interior_point.flow_angle - wall_point.flow_angle),
+            wall_point.prandtl_meyer_angle + s * (interior_point.prandtl_meyer_angle - wall_point.prandtl_meyer_angle),
+            specific_heat_ratio,
+        )
+    };
+
+    let mut point: Option<CharacteristicPoint> = None;
+    for _ in 0..CORRECTOR_ITERATIONS {
+        // the C+ slope is averaged with the previous estimate at the wall
+        let f = |s: f64| {
+            match interpolate(s) {
+                Ok(origin) => {
+                    let angle = match point {
+                        Some(previous) => 0.5 * ((origin.flow_angle + origin.mach_angle) + (previous.flow_angle + previous.mach_angle)),
+                        None => origin.flow_angle + origin.mach_angle,
+                    };
+                    wall_position.1 - (origin.y + angle.tan() * (wall_position.0 - origin.x))
+                }
+                Err(_) => f64::NAN,
+            }
+        };
+        let origin: CharacteristicPoint = interpolate(bisection(&f, 0.0, 1.0, Some(1e-12), None))?;
+        let partner: Option<CharacteristicPoint> = point.map(|previous| CharacteristicPoint { flow_angle: wall_angle, ..previous });
+        let prandtl_meyer_angle: f64 = -origin.plus_invariant() + wall_angle
+            + source_increment(&origin, partner.as_ref(), true, wall_position.0 - origin.x, wall_position.1 - origin.y);
+        point = Some(CharacteristicPoint::new(wall_position.0, wall_position.1, wall_angle, prandtl_meyer_angle, specific_heat_ratio)?);
+    }
+    Ok(point.ok_or(IsentropicFlowError::WhatTheFuck)?)
+}
+
+#[allow(clippy::type_complexity)]
+fn calc_corner_fan(max_wall_angle: f64, characteristic_lines: usize, specific_heat_ratio: f64) -> Result<(Vec<CharacteristicPoint>, Vec<Vec<CharacteristicPoint>>), CompressibleFlowError> {
+    // centred expansion at the throat corner reflected off the axis, indexed as
+    // in the planar minimum length nozzle
+    let n: usize = characteristic_lines;
+    let corner: Vec<CharacteristicPoint> = (1..=n)
+        .map(|i| {
+            let theta = max_wall_angle * i as f64 / n as f64;
+            CharacteristicPoint::new(0.0, 1.0, theta, theta, specific_heat_ratio)
+        })
+        .collect::<Result<_, _>>()?;
+
+    let mut net: Vec<Vec<CharacteristicPoint>> = Vec::with_capacity(n);
+    for j in 0..n {
+        let mut line: Vec<CharacteristicPoint> = Vec::with_capacity(j + 1);
+        for i in 0..=j {
+            let minus_point: CharacteristicPoint = if i == 0 { corner[j] } else { line[i - 1] };
+            let point: CharacteristicPoint = if i == j {
+                calc_axisymmetric_symmetry_point(&minus_point, specific_heat_ratio)?
+            } else {
+                calc_axisymmetric_interior_point(&minus_point, &net[j - 1][i], specific_heat_ratio)?
+            };
+            line.push(point);
+        }
+        net.push(line);
+    }
+    Ok((corner, net))
+}
+
+fn truncate_contour(wall: &[(f64, f64)], length_fraction: f64) -> Vec<(f64, f64)> {
+    let length: f64 = length_fraction * wall[wall.len() - 1].0;
+    let mut truncated: Vec<(f64, f64)> = vec![wall[0]];
+    for pair in wall.windows(2) {
+        if pair[1].0 < length {
+            truncated.push(pair[1]);
+        } else {
+            let fraction = (length - pair[0].0) / (pair[1].0 - pair[0].0);
+            if fraction > 1e-9 {
+                truncated.push((length, pair[0].1 + fraction * (pair[1].1 - pair[0].1)));
+            }
+            break;
+        }
+    }
+    truncated
+}
+
+fn calc_wall_angles(wall: &[(f64, f64)]) -> Vec<f64> {
+    // one sided at the ends, central between
+    let n: usize = wall.len();
+    (0..n).map(|i| {
+        let (a, b) = if i == 0 { (0, 1) } else if i == n - 1 { (n - 2, n - 1) } else { (i - 1, i + 1) };
+        (wall[b].1 - wall[a].1).atan2(wall[b].0 - wall[a].0)
+    }).collect()
+}
+
+fn intersect_wall(origin: (f64, f64), slope: f64, wall: &[(f64, f64)], wall_angles: &[f64]) -> Option<(f64, f64, f64)> {
+    // first crossing of a straight line from origin with the wall polyline
+    // downstream of origin, θ is interpolated between the vertex angles
+    let gap = |point: &(f64, f64)| point.1 - (origin.1 + slope * (point.0 - origin.0));
+    for i in 0..wall.len() - 1 {
+        if wall[i + 1].0 < origin.0 {
+            continue;
+        }
+        let (g0, g1) = (gap(&wall[i]), gap(&wall[i + 1]));
+        if g0 >= 0.0 && g1 <= 0.0 && g0 != g1 {
+            let fraction = g0 / (g0 - g1);
+            return Some((
+                wall[i].0 + fraction * (wall[i + 1].0 - wall[i].0),
+                wall[i].1 + fraction * (wall[i + 1].1 - wall[i].1),
+                wall_angles[i] + fraction * (wall_angles[i + 1] - wall_angles[i]),
+            ));
+        }
+    }
+    None
+}
+
+fn intersect_characteristic(origin: (f64, f64), slope: f64, line: &[CharacteristicPoint]) -> Option<(f64, f64, f64)> {
+    // crossing of a streamline from origin with a characteristic polyline, θ is
+    // interpolated along the crossed segment
+    let gap = |point: &CharacteristicPoint| point.y - (origin.1 + slope * (point.x - origin.0));
+    for pair in line.windows(2) {
+        let (g0, g1) = (gap(&pair[0]), gap(&pair[1]));
+        if g0.signum() != g1.signum() {
+            let fraction = g0 / (g0 - g1);
+            return Some((
+                pair[0].x + fraction * (pair[1].x - pair[0].x),
+                pair[0].y + fraction * (pair[1].y - pair[0].y),
+                pair[0].flow_angle + fraction * (pair[1].flow_angle - pair[0].flow_angle),
+            ));
+        }
+    }
+    None
+}
+
+fn interpolate_height(x: f64, wall: &[(f64, f64)]) -> f64 {
+    let i: usize = wall.windows(2).position(|pair| pair[1].0 >= x).unwrap_or(wall.len() - 2);
+    wall[i].1 + (x - wall[i].0) / (wall[i + 1].0 - wall[i].0) * (wall[i + 1].1 - wall[i].1)
+}
+
+fn interpolate_wall(x: f64, wall: &[(f64, f64)], wall_angles: &[f64]) -> (f64, f64, f64) {
+    // wall height and angle at a given x, the wall is single valued in x
+    let i: usize = wall.windows(2).position(|pair| pair[1].0 >= x).unwrap_or(wall.len() - 2);
+    let fraction: f64 = (x - wall[i].0) / (wall[i + 1].0 - wall[i].0);
+    (
+        x,
+        wall[i].1 + fraction * (wall[i + 1].1 - wall[i].1),
+        wall_angles[i] + fraction * (wall_angles[i + 1] - wall_angles[i]),
+    )
+}
+
+fn calc_segment_mass_flow(lower: &CharacteristicPoint, upper: &CharacteristicPoint, specific_heat_ratio: f64) -> Result<f64, CompressibleFlowError> {
+    // mass flow through the annulus swept by a straight segment, per ρ0 a0 r*²
+    let mach_number: f64 = 0.5 * (lower.mach_number + upper.mach_number);
+    let flow_angle: f64 = 0.5 * (lower.flow_angle + upper.flow_angle);
+    let radius: f64 = 0.5 * (lower.y + upper.y);
+    let (dx, dy) = (upper.x - lower.x, upper.y - lower.y);
+    Ok(2.0 * PI * radius * mass_flux(mach_number, specific_heat_ratio)? * (flow_angle.cos() * dy - flow_angle.sin() * dx))
+}
+
+fn mass_flux(mach_number: f64, specific_heat_ratio: f64) -> Result<f64, CompressibleFlowError> {
+    // ρV / (ρ0 a0)
+    let density_ratio: f64 = isentropic::calc_density_ratio_from_mach(mach_number, specific_heat_ratio)?;
+    let temperature_ratio: f64 = isentropic::calc_temperature_ratio_from_mach(mach_number, specific_heat_ratio)?;
+    Ok(density_ratio * mach_number * temperature_ratio.sqrt())
+}
+
+fn source_increment(known: &CharacteristicPoint, new: Option<&CharacteristicPoint>, plus: bool, dx: f64, dy: f64) -> f64 {
+    // axisymmetric source term integrated along a characteristic, evaluated with
+    // the properties averaged over its two ends which keeps the corrector stable
+    // next to the axis where sinθ / y is singular, the dy form
+    // sinθ sinμ / (y sin(θ ± μ)) dy is used when the characteristic is steep to
+    // avoid dividing by cos(θ ± μ) near sonic flow
+    let (y, flow_angle, mach_angle) = match new {
+        Some(point) => (
+            0.5 * (known.y + point.y),
+            0.5 * (known.flow_angle + point.flow_angle),
+            0.5 * (known.mach_angle + point.mach_angle),
+        ),
+        None => (known.y, known.flow_angle, known.mach_angle),
+    };
+    if y.abs() < 1e-9 {
+        return 0.0;
+    }
+    let angle: f64 = if plus { flow_angle + mach_angle } else { flow_angle - mach_angle };
+    if angle.sin().abs() > angle.cos().abs() {
+        flow_angle.sin() * mach_angle.sin() / (y * angle.sin()) * dy
+    } else {
+        flow_angle.sin() * mach_angle.sin() / (y * angle.cos()) * dx
+    }
+}
+
+const CORRECTOR_ITERATIONS: usize = 4;
+const MAX_MARCHING_LINES: usize = 100000;
+const THROAT_ARC_RADIUS: f64 = 0.382; // rao's downstream throat radius over r*
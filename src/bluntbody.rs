@@ -0,0 +1,127 @@
+use crate::isentropic::{valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::obliqueshock;
+
+
+// billig's correlations for the detached bow shock ahead of a blunt body,
+// fitted to perfect gas γ = 1.4 data, lengths are in the units of the body
+// radius and x runs downstream from the shock vertex on the axis, so the body
+// nose sits at x = standoff_distance
+#[derive(Debug, Clone, Copy)]
+pub enum BluntBody {
+    Sphere,   // sphere cone, axisymmetric
+    Cylinder, // cylinder wedge, planar
+}
+
+#[derive(Debug)]
+pub struct BilligShock {
+    pub body: BluntBody,
+    pub freestream_mach: f64,
+    pub body_radius: f64,       // R
+    pub standoff_distance: f64, // Δ on the axis
+    pub shock_radius: f64,      // Rc, radius of curvature at the vertex
+    pub mach_angle: f64,        // μ, asymptotic shock angle far from the body
+    pub(crate) specific_heat_ratio: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ShockPoint {
+    pub x: f64,                         // downstream of the shock vertex
+    pub y: f64,                         // distance from the axis
+    pub shock_angle: f64,               // β between the shock and the freestream
+    pub deflection_angle: f64,          // θ of the flow just behind the shock
+    pub downstream_mach: f64,           // M2
+    pub pressure_ratio: f64,            // p2 / p1
+    pub density_ratio: f64,             // ρ2 / ρ1
+    pub temperature_ratio: f64,         // T2 / T1
+    pub stagnation_pressure_ratio: f64, // p02 / p01
+}
+
+impl BilligShock {
+    pub fn new(body: BluntBody, freestream_mach: f64, body_radius: f64, specific_heat_ratio: f64) -> Result<BilligShock, CompressibleFlowError> {
+        if !valid_specific_heat_ratio(specific_heat_ratio) {
+            return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+        }
+        if freestream_mach <= 1.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        if body_radius <= 0.0 {
+            return Err(CompressibleFlowError::InvalidGeometry);
+        }
+        Ok(BilligShock {
+            body,
+            freestream_mach,
+            body_radius,
+            standoff_distance: body_radius * calc_standoff_distance(body, freestream_mach)?,
+            shock_radius: body_radius * calc_shock_radius(body, freestream_mach)?,
+            mach_angle: (1.0 / freestream_mach).asin(),
+            specific_heat_ratio,
+        })
+    }
+
+    pub fn shock_position(&self, y: f64) -> f64 {
+        // x = Rc cot²μ (√(1 + y² tan²μ / Rc²) - 1), a hyperbola with the
+        // freestream mach lines as asymptotes
+        let tan_squared: f64 = self.mach_angle.tan().powi(2);
+        self.shock_radius / tan_squared * ((1.0 + (y / self.shock_radius).powi(2) * tan_squared).sqrt() - 1.0)
+    }
+
+    pub fn shock_angle(&self, y: f64) -> f64 {
+        // tan β = dy/dx = Rc √(1 + y² tan²μ / Rc²) / y, normal on the axis
+        let tan_squared: f64 = self.mach_angle.tan().powi(2);
+        (self.shock_radius * (1.0 + (y / self.shock_radius).powi(2) * tan_squared).sqrt()).atan2(y.abs())
+    }
+
+    pub fn point(&self, y: f64) -> Result<ShockPoint, CompressibleFlowError> {
+        // the state just behind the local shock follows from the oblique shock
+        // relations at the local shock angle, subsonic near the axis where
+        // ObliqueShock itself only takes the weak supersonic branch
+        let shock_angle: f64 = self.shock_angle(y);
+        let (mach, gamma) = (self.freestream_mach, self.specific_heat_ratio);
+        Ok(ShockPoint {
+            x: self.shock_position(y),
+            y,
+            shock_angle,
+            deflection_angle: obliqueshock::calc_deflection_angle(mach, shock_angle, gamma)?,
+            downstream_mach: obliqueshock::calc_downstream_mach_from_shock_angle(mach, shock_angle, gamma)?,
+            pressure_ratio: obliqueshock::calc_pressure_ratio(mach, shock_angle, gamma)?,
+            density_ratio: obliqueshock::calc_density_ratio(mach, shock_angle, gamma)?,
+            temperature_ratio: obliqueshock::calc_temperature_ratio(mach, shock_angle, gamma)?,
+            stagnation_pressure_ratio: obliqueshock::calc_stagnation_pressure_ratio(mach, shock_angle, gamma)?,
+        })
+    }
+
+    pub fn shape(&self, max_y: f64, points: usize) -> Result<Vec<ShockPoint>, CompressibleFlowError> {
+        // evenly spaced points from the axis out to max_y
+        if points < 2 {
+            return Err(CompressibleFlowError::InvalidGridPoints);
+        }
+        if max_y <= 0.0 {
+            return Err(CompressibleFlowError::InvalidGeometry);
+        }
+        (0..points).map(|i| self.point(max_y * i as f64 / (points - 1) as f64)).collect()
+    }
+}
+
+pub fn calc_standoff_distance(body: BluntBody, freestream_mach: f64) -> Result<f64, CompressibleFlowError> {
+    // Δ / R, 0.143 exp(3.24 / M²) for a sphere and 0.386 exp(4.67 / M²) for a cylinder
+    if freestream_mach <= 1.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    Ok(match body {
+        BluntBody::Sphere => 0.143 * (3.24 / freestream_mach.powi(2)).exp(),
+        BluntBody::Cylinder => 0.386 * (4.67 / freestream_mach.powi(2)).exp(),
+    })
+}
+
+pub fn calc_shock_radius(body: BluntBody, freestream_mach: f64) -> Result<f64, CompressibleFlowError> {
+    // Rc / R, 1.143 exp(0.54 / (M - 1)^1.2) for a sphere and
+    // 1.386 exp(1.8 / (M - 1)^0.75) for a cylinder
+    if freestream_mach <= 1.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    Ok(match body {
+        BluntBody::Sphere => 1.143 * (0.54 / (freestream_mach - 1.0).powf(1.2)).exp(),
+        BluntBody::Cylinder => 1.386 * (1.8 / (freestream_mach - 1.0).powf(0.75)).exp(),
+    })
+}
@@ -0,0 +1,186 @@
+use crate::isentropic::IsentropicFlowError;
+use crate::error::CompressibleFlowError;
+use crate::obliqueshock::ObliqueShock;
+use crate::taylormaccoll::SupersonicCone;
+use crate::gas::GasProperties;
+
+
+// compressible flat plate skin friction and heating from the edge state, SI
+// units, coefficients are local and based on the edge dynamic pressure
+const DEFAULT_PRANDTL_NUMBER: f64 = 0.71;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundaryLayerRegime {
+    Laminar,
+    Turbulent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SkinFrictionMethod {
+    ReferenceTemperature, // eckert, laminar or turbulent
+    VanDriestII,          // turbulent only
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct EdgeConditions {
+    pub mach_number: f64,
+    pub temperature: f64, // K
+    pub pressure: f64,    // Pa
+    pub gas: GasProperties,
+}
+
+#[derive(Debug)]
+pub struct FlatPlateBoundaryLayer {
+    pub regime: BoundaryLayerRegime,
+    pub reynolds_number: f64,           // Re_x with edge properties
+    pub reference_temperature: f64,     // eckert T*, K
+    pub recovery_temperature: f64,      // adiabatic wall temperature, K
+    pub skin_friction_coefficient: f64, // cf = τw / (ρe ue² / 2)
+    pub wall_shear_stress: f64,         // Pa
+    pub stanton_number: f64,            // qw / (ρe ue cp (Tr - Tw))
+    pub heat_flux: f64,                 // W/m², positive into the wall
+}
+
+impl EdgeConditions {
+    pub fn new(mach_number: f64, temperature: f64, pressure: f64, gas: GasProperties) -> Result<EdgeConditions, CompressibleFlowError> {
+        if mach_number < 0.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        if temperature <= 0.0 {
+            return Err(IsentropicFlowError::InvalidTemperatureRatio.into());
+        }
+        if pressure <= 0.0 {
+            return Err(IsentropicFlowError::InvalidPressureRatio.into());
+        }
+        Ok(EdgeConditions { mach_number, temperature, pressure, gas })
+    }
+
+    pub fn behind_oblique_shock(shock: &ObliqueShock, freestream_temperature: f64, freestream_pressure: f64, gas: GasProperties) -> Result<EdgeConditions, CompressibleFlowError> {
+        // wedge or ramp surface downstream of an attached shock
+        EdgeConditions::new(shock.downstream_mach, freestream_temperature * shock.temperature_ratio, freestream_pressure * shock.pressure_ratio, gas)
+    }
+
+    pub fn on_cone_surface(cone: &SupersonicCone, freestream_temperature: f64, freestream_pressure: f64, gas: GasProperties) -> Result<EdgeConditions, CompressibleFlowError> {
+        EdgeConditions::new(cone.surface_mach, freestream_temperature * cone.surface_temperature_ratio, freestream_pressure * cone.surface_pressure_ratio, gas)
+    }
+
+    pub fn density(&self) -> f64 {
+        self.pressure / (self.gas.gas_constant() * self.temperature)
+    }
+
+    pub fn velocity(&self) -> f64 {
+        self.mach_number * (self.gas.specific_heat_ratio * self.gas.gas_constant() * self.temperature).sqrt()
+    }
+
+    pub fn unit_reynolds_number(&self) -> f64 {
+        // ρe ue / μe, 1/m
+        self.density() * self.velocity() / self.gas.viscosity(self.temperature)
+    }
+}
+
+impl FlatPlateBoundaryLayer {
+    pub fn new(
+        edge: &EdgeConditions,
+        distance: f64,         // from the leading edge, m
+        wall_temperature: f64, // K
+        regime: BoundaryLayerRegime,
+        method: SkinFrictionMethod,
+        prandtl_number: Option<f64>,
+    ) -> Result<FlatPlateBoundaryLayer, CompressibleFlowError> {
+        // heating follows the reynolds analogy St = (cf / 2) Pr^-2/3
+        let prandtl_number: f64 = prandtl_number.unwrap_or(DEFAULT_PRANDTL_NUMBER);
+        if prandtl_number <= 0.0 {
+            return Err(CompressibleFlowError::InvalidFlowRegime);
+        }
+        if distance <= 0.0 {
+            return Err(CompressibleFlowError::InvalidGeometry);
+        }
+        if wall_temperature <= 0.0 {
+            return Err(IsentropicFlowError::InvalidTemperatureRatio.into());
+        }
+        if edge.mach_number <= 0.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        let reynolds_number: f64 = edge.unit_reynolds_number() * distance;
+        let recovery_temperature: f64 = calc_recovery_temperature(edge.temperature, edge.mach_number, regime, Some(prandtl_number), edge.gas.specific_heat_ratio);
+        let reference_temperature: f64 = calc_reference_temperature(edge.temperature, wall_temperature, recovery_temperature);
+
+        let skin_friction_coefficient: f64 = match (method, regime) {
+            (SkinFrictionMethod::ReferenceTemperature, _) => {
+                // incompressible blasius or power law at T*, with ρ* / ρe = Te / T*
+                let density_ratio: f64 = edge.temperature / reference_temperature;
+                let viscosity_ratio: f64 = edge.gas.viscosity(reference_temperature) / edge.gas.viscosity(edge.temperature);
+                match regime {
+                    BoundaryLayerRegime::Laminar => 0.664 * (density_ratio * viscosity_ratio).sqrt() / reynolds_number.sqrt(),
+                    BoundaryLayerRegime::Turbulent => 0.0592 * density_ratio.powf(0.8) * viscosity_ratio.powf(0.2) / reynolds_number.powf(0.2),
+                }
+            }
+            (SkinFrictionMethod::VanDriestII, BoundaryLayerRegime::Turbulent) => {
+                calc_van_driest_skin_friction(edge, reynolds_number, wall_temperature, recovery_temperature)
+            }
+            (SkinFrictionMethod::VanDriestII, BoundaryLayerRegime::Laminar) => {
+                return Err(CompressibleFlowError::InvalidFlowRegime);
+            }
+        };
+
+        let dynamic_pressure: f64 = 0.5 * edge.density() * edge.velocity().powi(2);
+        let stanton_number: f64 = calc_stanton_number(skin_friction_coefficient, Some(prandtl_number));
+        let specific_heat: f64 = edge.gas.specific_heat_ratio / (edge.gas.specific_heat_ratio - 1.0) * edge.gas.gas_constant();
+
+        Ok(FlatPlateBoundaryLayer {
+            regime,
+            reynolds_number,
+            reference_temperature,
+            recovery_temperature,
+            skin_friction_coefficient,
+            wall_shear_stress: skin_friction_coefficient * dynamic_pressure,
+            stanton_number,
+            heat_flux: stanton_number * edge.density() * edge.velocity() * specific_heat * (recovery_temperature - wall_temperature),
+        })
+    }
+}
+
+pub fn calc_recovery_factor(regime: BoundaryLayerRegime, prandtl_number: Option<f64>) -> f64 {
+    // √Pr laminar, ∛Pr turbulent
+    let prandtl_number: f64 = prandtl_number.unwrap_or(DEFAULT_PRANDTL_NUMBER);
+    match regime {
+        BoundaryLayerRegime::Laminar => prandtl_number.sqrt(),
+        BoundaryLayerRegime::Turbulent => prandtl_number.cbrt(),
+    }
+}
+
+pub fn calc_recovery_temperature(edge_temperature: f64, edge_mach: f64, regime: BoundaryLayerRegime, prandtl_number: Option<f64>, specific_heat_ratio: f64) -> f64 {
+    // Tr = Te (1 + r (γ - 1) M² / 2)
+    let recovery_factor: f64 = calc_recovery_factor(regime, prandtl_number);
+    edge_temperature * (1.0 + recovery_factor * (specific_heat_ratio - 1.0) / 2.0 * edge_mach.powi(2))
+}
+
+pub fn calc_reference_temperature(edge_temperature: f64, wall_temperature: f64, recovery_temperature: f64) -> f64 {
+    // eckert T* = 0.28 Te + 0.5 Tw + 0.22 Tr
+    0.28 * edge_temperature + 0.5 * wall_temperature + 0.22 * recovery_temperature
+}
+
+pub fn calc_stanton_number(skin_friction_coefficient: f64, prandtl_number: Option<f64>) -> f64 {
+    // colburn reynolds analogy
+    skin_friction_coefficient / 2.0 * prandtl_number.unwrap_or(DEFAULT_PRANDTL_NUMBER).powf(-2.0 / 3.0)
+}
+
+fn calc_van_driest_skin_friction(edge: &EdgeConditions, reynolds_number: f64, wall_temperature: f64, recovery_temperature: f64) -> f64 {
+    // cf = cf_inc(Fx Re) / Fc with hopkins and inouye's form of the transformation
+    //     Fc = (Tr / Te - 1) / (asin α + asin β)², Fx = (μe / μw) / Fc
+    // and white's cf_inc = 0.455 / ln²(0.06 Re)
+    let wall_ratio: f64 = wall_temperature / edge.temperature;
+    let a: f64 = ((recovery_temperature / edge.temperature - 1.0) / wall_ratio).sqrt();
+    let b: f64 = recovery_temperature / wall_temperature - 1.0;
+    let compressibility: f64 = if a < 1e-8 {
+        // low speed limit with a heated or cooled wall
+        ((1.0 + wall_ratio.sqrt()) / 2.0).powi(2)
+    } else {
+        let root: f64 = (b.powi(2) + 4.0 * a.powi(2)).sqrt();
+        let alpha: f64 = ((2.0 * a.powi(2) - b) / root).clamp(-1.0, 1.0);
+        let beta: f64 = (b / root).clamp(-1.0, 1.0);
+        (recovery_temperature / edge.temperature - 1.0) / (alpha.asin() + beta.asin()).powi(2)
+    };
+    let reynolds_factor: f64 = edge.gas.viscosity(edge.temperature) / edge.gas.viscosity(wall_temperature) / compressibility;
+    0.455 / (0.06 * reynolds_factor * reynolds_number).ln().powi(2) / compressibility
+}
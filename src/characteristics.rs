@@ -0,0 +1,356 @@
+use crate::isentropic::{self, valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+
+
+#[derive(Debug, Clone, Copy)]
+pub struct CharacteristicPoint {
+    pub x: f64,
+    pub y: f64,
+    pub flow_angle: f64,          // θ
+    pub prandtl_meyer_angle: f64, // ν
+    pub mach_number: f64,         // M
+    pub mach_angle: f64,          // μ
+}
+
+impl CharacteristicPoint {
+    pub fn new(x: f64, y: f64, flow_angle: f64, prandtl_meyer_angle: f64, specific_heat_ratio: f64) -> Result<CharacteristicPoint, CompressibleFlowError> {
+        let mach_number: f64 = isentropic::calc_mach_from_prandtl_meyer_angle(prandtl_meyer_angle, specific_heat_ratio)?;
+        let mach_angle: f64 = isentropic::calc_mach_angle_from_mach(mach_number)?;
+        Ok(CharacteristicPoint {
+            x,
+            y,
+            flow_angle,
+            prandtl_meyer_angle,
+            mach_number,
+            mach_angle,
+        })
+    }
+
+    pub fn minus_invariant(&self) -> f64 {
+        // K- = θ + ν, constant along a C- (right running) characteristic
+        self.flow_angle + self.prandtl_meyer_angle
+    }
+
+    pub fn plus_invariant(&self) -> f64 {
+        // K+ = θ - ν, constant along a C+ (left running) characteristic
+        self.flow_angle - self.prandtl_meyer_angle
+    }
+}
+
+#[derive(Debug)]
+pub struct MinimumLengthNozzle {
+    // lengths are nondimensionalised by the throat half height, the
+    // nozzle centreline lies along y = 0 and the throat sits at x = 0
+    pub exit_mach: f64,
+    pub wall_contour: Vec<(f64, f64)>,              // (x, y) from the throat corner to the exit lip
+    pub characteristic_net: Vec<CharacteristicPoint>, // every interior, centreline and wall node
+    pub max_wall_angle: f64,                         // θw,max = ν(Me) / 2
+    pub length: f64,                                 // L / h*
+    pub area_ratio: f64,                             // Ae / A* from the computed exit height
+}
+
+impl MinimumLengthNozzle {
+    pub fn from_exit_mach(exit_mach: f64, characteristic_lines: usize, specific_heat_ratio: f64) -> Result<MinimumLengthNozzle, CompressibleFlowError> {
+        // planar minimum length nozzle with a sharp throat corner, the expansion
+        // fan at the corner is split into a finite number of characteristic lines
+        // and reflected off the centreline (anderson, modern compressible flow, 11.7)
+        if !valid_specific_heat_ratio(specific_heat_ratio) {
+            return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+        }
+        if exit_mach <= 1.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        if characteristic_lines < 2 {
+            return Err(CompressibleFlowError::InvalidNumberOfCharacteristics);
+        }
+        let n: usize = characteristic_lines;
+
+        let exit_prandtl_meyer_angle: f64 = isentropic::prandtl_meyer_function(exit_mach, specific_heat_ratio)?;
+        let max_wall_angle: f64 = exit_prandtl_meyer_angle / 2.0;
+
+        // flow angles of the fan leaving the corner, in the simple wave region K+ = 0 so ν = θ
+        let fan_angles: Vec<f64> = (1..=n).map(|i| max_wall_angle * i as f64 / n as f64).collect();
+        let corner: Vec<CharacteristicPoint> = fan_angles.iter()
+            .map(|&theta| CharacteristicPoint::new(0.0, 1.0, theta, theta, specific_heat_ratio))
+            .collect::<Result<_, _>>()?;
+
+        // net[j][i] is where the C- from fan line j crosses the C+ reflected
+        // from the centreline by fan line i, with i <= j and i == j on the centreline
+        let mut net: Vec<Vec<CharacteristicPoint>> = Vec::with_capacity(n);
+        for j in 0..n {
+            let mut line: Vec<CharacteristicPoint> = Vec::with_capacity(j + 1);
+            for i in 0..=j {
+                // upstream point along this C-
+                let minus_point: CharacteristicPoint = if i == 0 { corner[j] } else { line[i - 1] };
+                let point: CharacteristicPoint = if i == j {
+                    calc_symmetry_point(&minus_point, specific_heat_ratio)?
+                } else {
+                    calc_interior_point(&minus_point, &net[j - 1][i], specific_heat_ratio)?
+                };
+                line.push(point);
+            }
+            net.push(line);
+        }
+
+        // each C+ leaves the last fan line and is cancelled at the wall, so the
+        // wall takes the flow angle carried by the incident characteristic
+        let mut wall_points: Vec<CharacteristicPoint> = Vec::with_capacity(n);
+        let mut previous_wall: CharacteristicPoint = corner[n - 1];
+        for plus_point in net[n - 1].iter() {
+            let wall_point: CharacteristicPoint = calc_cancellation_wall_point(&previous_wall, plus_point, specific_heat_ratio)?;
+            wall_points.push(wall_point);
+            previous_wall = wall_point;
+        }
+
+        let mut wall_contour: Vec<(f64, f64)> = vec![(0.0, 1.0)];
+        wall_contour.extend(wall_points.iter().map(|point| (point.x, point.y)));
+        let (length, exit_height) = *wall_contour.last().ok_or(IsentropicFlowError::WhatTheFuck)?;
+
+        let mut characteristic_net: Vec<CharacteristicPoint> = net.into_iter().flatten().collect();
+        characteristic_net.extend(wall_points);
+
+        Ok(MinimumLengthNozzle {
+            exit_mach,
+            wall_contour,
+            characteristic_net,
+            max_wall_angle,
+            length,
+            area_ratio: exit_height,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct CharacteristicNet {
+    // lines alternate between a full line ending on both boundaries and a
+    // staggered line of interior points, each ordered from the lower boundary up
+    pub lines: Vec<Vec<CharacteristicPoint>>,
+    pub shock_formation: Option<(f64, f64)>, // where characteristics of one family first cross
+}
+
+impl CharacteristicNet {
+    pub fn upper_wall_points(&self) -> Vec<CharacteristicPoint> {
+        let full_length: usize = self.lines[0].len();
+        self.lines.iter().filter(|line| line.len() == full_length).map(|line| line[full_length - 1]).collect()
+    }
+
+    pub fn lower_boundary_points(&self) -> Vec<CharacteristicPoint> {
+        // lower wall, or the symmetry line when no lower wall was given
+        let full_length: usize = self.lines[0].len();
+        self.lines.iter().filter(|line| line.len() == full_length).map(|line| line[0]).collect()
+    }
+}
+
+pub fn calc_uniform_start_line(x: f64, lower_y: f64, upper_y: f64, mach_number: f64, flow_angle: f64, points: usize, specific_heat_ratio: f64) -> Result<Vec<CharacteristicPoint>, CompressibleFlowError> {
+    // straight start line across a uniform supersonic stream
+    if points < 3 {
+        return Err(CompressibleFlowError::InvalidNumberOfCharacteristics);
+    }
+    if upper_y <= lower_y {
+        return Err(CompressibleFlowError::InvalidGeometry);
+    }
+    let prandtl_meyer_angle: f64 = isentropic::prandtl_meyer_function(mach_number, specific_heat_ratio)?;
+    (0..points)
+        .map(|i| {
+            let y = lower_y + (upper_y - lower_y) * i as f64 / (points - 1) as f64;
+            CharacteristicPoint::new(x, y, flow_angle, prandtl_meyer_angle, specific_heat_ratio)
+        })
+        .collect()
+}
+
+pub fn march_characteristics(start_line: &[CharacteristicPoint], upper_wall: &[(f64, f64)], lower_wall: Option<&[(f64, f64)]>, specific_heat_ratio: f64, max_lines: Option<usize>) -> Result<CharacteristicNet, CompressibleFlowError> {
+    // marches a planar supersonic flow downstream of a start line running from
+    // the lower boundary to the upper wall, the walls are polylines given as
+    // (x, y) with increasing x and a missing lower wall means a symmetry line
+    // along y = 0, marching stops when a wall ends or the net folds over
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    if start_line.len() < 3 {
+        return Err(CompressibleFlowError::InvalidNumberOfCharacteristics);
+    }
+    if start_line.windows(2).any(|pair| pair[1].y <= pair[0].y) {
+        return Err(CompressibleFlowError::InvalidGeometry);
+    }
+    for wall in std::iter::once(upper_wall).chain(lower_wall) {
+        if wall.len() < 2 || wall.windows(2).any(|pair| pair[1].0 <= pair[0].0) {
+            return Err(CompressibleFlowError::InvalidGeometry);
+        }
+    }
+    if lower_wall.is_none() && start_line[0].y.abs() > 1e-9 {
+        return Err(CompressibleFlowError::InvalidGeometry);
+    }
+    let max_lines: usize = max_lines.unwrap_or(10000);
+
+    let mut lines: Vec<Vec<CharacteristicPoint>> = vec![start_line.to_vec()];
+    let mut shock_formation: Option<(f64, f64)> = None;
+
+    while lines.len() < max_lines {
+        let previous: &Vec<CharacteristicPoint> = &lines[lines.len() - 1];
+        let full: bool = previous.len() == start_line.len();
+        let mut line: Vec<CharacteristicPoint> = Vec::with_capacity(start_line.len());
+
+        if !full {
+            // C- from the lowest interior point reaches the lower boundary
+            let lower_point: Option<CharacteristicPoint> = match lower_wall {
+                Some(wall) => calc_wall_point(&previous[0], wall, false, specific_heat_ratio)?,
+                None => Some(calc_symmetry_point(&previous[0], specific_heat_ratio)?),
+            };
+            match lower_point {
+                Some(point) => line.push(point),
+                None => break,
+            }
+        }
+        // the C+ comes from the lower neighbour and the C- from the upper one
+        for pair in previous.windows(2) {
+            line.push(calc_interior_point(&pair[1], &pair[0], specific_heat_ratio)?);
+        }
+        if !full {
+            match calc_wall_point(&previous[previous.len() - 1], upper_wall, true, specific_heat_ratio)? {
+                Some(point) => line.push(point),
+                None => break,
+            }
+        }
+
+        if let Some(position) = find_fold(previous, &line) {
+            shock_formation = Some(position);
+            break;
+        }
+        lines.push(line);
+    }
+
+    Ok(CharacteristicNet {
+        lines,
+        shock_formation,
+    })
+}
+
+pub fn calc_wall_point(incident_point: &CharacteristicPoint, wall: &[(f64, f64)], upper: bool, specific_heat_ratio: f64) -> Result<Option<CharacteristicPoint>, CompressibleFlowError> {
+    // direct wall point, a C+ runs up to an upper wall and a C- down to a lower
+    // wall, the wall fixes θ and the incident invariant fixes ν, returns none
+    // if the characteristic runs past the end of the wall
+    let characteristic_angle = |point: &CharacteristicPoint| if upper {
+        point.flow_angle + point.mach_angle
+    } else {
+        point.flow_angle - point.mach_angle
+    };
+
+    let mut slope: f64 = characteristic_angle(incident_point).tan();
+    let mut point: Option<CharacteristicPoint> = None;
+    // predictor with the upstream slope, corrector with the averaged slope
+    for _ in 0..2 {
+        let (x, y, flow_angle) = match intersect_polyline((incident_point.x, incident_point.y), slope, wall) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let prandtl_meyer_angle: f64 = if upper {
+            flow_angle - incident_point.plus_invariant()
+        } else {
+            incident_point.minus_invariant() - flow_angle
+        };
+        let new_point: CharacteristicPoint = CharacteristicPoint::new(x, y, flow_angle, prandtl_meyer_angle, specific_heat_ratio)?;
+        slope = (0.5 * (characteristic_angle(incident_point) + characteristic_angle(&new_point))).tan();
+        point = Some(new_point);
+    }
+    Ok(point)
+}
+
+fn intersect_polyline(origin: (f64, f64), slope: f64, wall: &[(f64, f64)]) -> Option<(f64, f64, f64)> {
+    // first crossing of a straight line from origin with a wall polyline
+    // downstream of origin, θ is the angle of the crossed segment
+    let gap = |point: &(f64, f64)| point.1 - (origin.1 + slope * (point.0 - origin.0));
+    for pair in wall.windows(2) {
+        if pair[1].0 <= origin.0 {
+            continue;
+        }
+        let (g0, g1) = (gap(&pair[0]), gap(&pair[1]));
+        if g0 == g1 || g0 * g1 > 0.0 {
+            continue;
+        }
+        let fraction: f64 = g0 / (g0 - g1);
+        let x: f64 = pair[0].0 + fraction * (pair[1].0 - pair[0].0);
+        if x > origin.0 {
+            let y: f64 = pair[0].1 + fraction * (pair[1].1 - pair[0].1);
+            return Some((x, y, (pair[1].1 - pair[0].1).atan2(pair[1].0 - pair[0].0)));
+        }
+    }
+    None
+}
+
+fn find_fold(previous: &[CharacteristicPoint], line: &[CharacteristicPoint]) -> Option<(f64, f64)> {
+    // the strip between two lines is split into triangles, each segment of the
+    // previous line must see the new line downstream and each segment of the new
+    // line must see the previous one upstream, a flipped triangle means two
+    // characteristics of the same family have crossed
+    let cross = |a: &CharacteristicPoint, b: &CharacteristicPoint, c: &CharacteristicPoint| {
+        (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+    };
+    // offset of the new line index against the previous line index
+    let offset: usize = if line.len() < previous.len() { 0 } else { 1 };
+
+    for (j, pair) in previous.windows(2).enumerate() {
+        let apex: &CharacteristicPoint = &line[j + offset];
+        if cross(&pair[0], &pair[1], apex) >= 0.0 {
+            return Some((apex.x, apex.y));
+        }
+    }
+    for (j, pair) in line.windows(2).enumerate() {
+        // the previous point between the two new ones
+        let apex: &CharacteristicPoint = if offset == 0 { &previous[j + 1] } else { &previous[j] };
+        if cross(&pair[0], &pair[1], apex) <= 0.0 {
+            return Some((0.5 * (pair[0].x + pair[1].x), 0.5 * (pair[0].y + pair[1].y)));
+        }
+    }
+    None
+}
+
+pub fn calc_interior_point(minus_point: &CharacteristicPoint, plus_point: &CharacteristicPoint, specific_heat_ratio: f64) -> Result<CharacteristicPoint, CompressibleFlowError> {
+    // minus_point lies upstream on the C- through the new point, plus_point
+    // lies upstream on the C+ through it
+    let flow_angle: f64 = (minus_point.minus_invariant() + plus_point.plus_invariant()) / 2.0;
+    let prandtl_meyer_angle: f64 = (minus_point.minus_invariant() - plus_point.plus_invariant()) / 2.0;
+    let mut point: CharacteristicPoint = CharacteristicPoint::new(0.0, 0.0, flow_angle, prandtl_meyer_angle, specific_heat_ratio)?;
+
+    // characteristics approximated as straight lines with averaged slopes
+    let minus_slope: f64 = (0.5 * ((minus_point.flow_angle - minus_point.mach_angle) + (point.flow_angle - point.mach_angle))).tan();
+    let plus_slope: f64 = (0.5 * ((plus_point.flow_angle + plus_point.mach_angle) + (point.flow_angle + point.mach_angle))).tan();
+    let (x, y) = intersect_lines((minus_point.x, minus_point.y), minus_slope, (plus_point.x, plus_point.y), plus_slope)?;
+    point.x = x;
+    point.y = y;
+    Ok(point)
+}
+
+pub fn calc_symmetry_point(minus_point: &CharacteristicPoint, specific_heat_ratio: f64) -> Result<CharacteristicPoint, CompressibleFlowError> {
+    // C- reflecting off the centreline, θ = 0 there so ν = K-
+    let prandtl_meyer_angle: f64 = minus_point.minus_invariant();
+    let mut point: CharacteristicPoint = CharacteristicPoint::new(0.0, 0.0, 0.0, prandtl_meyer_angle, specific_heat_ratio)?;
+
+    let minus_slope: f64 = (0.5 * ((minus_point.flow_angle - minus_point.mach_angle) + (point.flow_angle - point.mach_angle))).tan();
+    if minus_slope >= 0.0 {
+        return Err(IsentropicFlowError::MathError.into());
+    }
+    point.x = minus_point.x - minus_point.y / minus_slope;
+    Ok(point)
+}
+
+fn calc_cancellation_wall_point(previous_wall: &CharacteristicPoint, plus_point: &CharacteristicPoint, specific_heat_ratio: f64) -> Result<CharacteristicPoint, CompressibleFlowError> {
+    // design wall point, the wall is turned to the flow angle of the incident
+    // C+ so that no wave is reflected
+    let mut point: CharacteristicPoint = CharacteristicPoint::new(0.0, 0.0, plus_point.flow_angle, plus_point.prandtl_meyer_angle, specific_heat_ratio)?;
+
+    let wall_slope: f64 = (0.5 * (previous_wall.flow_angle + point.flow_angle)).tan();
+    let plus_slope: f64 = (0.5 * ((plus_point.flow_angle + plus_point.mach_angle) + (point.flow_angle + point.mach_angle))).tan();
+    let (x, y) = intersect_lines((previous_wall.x, previous_wall.y), wall_slope, (plus_point.x, plus_point.y), plus_slope)?;
+    point.x = x;
+    point.y = y;
+    Ok(point)
+}
+
+pub(crate) fn intersect_lines(a: (f64, f64), slope_a: f64, b: (f64, f64), slope_b: f64) -> Result<(f64, f64), CompressibleFlowError> {
+    if (slope_a - slope_b).abs() < 1e-12 {
+        return Err(IsentropicFlowError::MathError.into());
+    }
+    let x: f64 = (b.1 - a.1 + slope_a * a.0 - slope_b * b.0) / (slope_a - slope_b);
+    let y: f64 = a.1 + slope_a * (x - a.0);
+    Ok((x, y))
+}
@@ -0,0 +1,79 @@
+use crate::isentropic::{self, valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::numerics::bisection;
+
+
+#[derive(Debug, Clone, Copy)]
+pub enum CompressibilityCorrection {
+    PrandtlGlauert, // Cp = Cp0 / β
+    KarmanTsien,    // Cp = Cp0 / (β + M² Cp0 / (2 (1 + β)))
+    Laitone,        // Cp = Cp0 / (β + M² (1 + (γ - 1) M² / 2) Cp0 / (2 β))
+}
+
+pub fn calc_compressible_pressure_coefficient(incompressible_pressure_coefficient: f64, mach_number: f64, correction: CompressibilityCorrection, specific_heat_ratio: f64) -> Result<f64, CompressibleFlowError> {
+    // corrects a low speed pressure coefficient to a subsonic freestream mach number
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    if !(0.0..1.0).contains(&mach_number) {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    let cp0: f64 = incompressible_pressure_coefficient;
+    let beta: f64 = (1.0 - mach_number.powi(2)).sqrt();
+
+    let denominator: f64 = match correction {
+        CompressibilityCorrection::PrandtlGlauert => beta,
+        CompressibilityCorrection::KarmanTsien => beta + mach_number.powi(2) / (1.0 + beta) * cp0 / 2.0,
+        CompressibilityCorrection::Laitone => {
+            beta + mach_number.powi(2) * (1.0 + (specific_heat_ratio - 1.0) / 2.0 * mach_number.powi(2)) / (2.0 * beta) * cp0
+        }
+    };
+    if denominator <= 0.0 {
+        // the correction is singular before this mach number is reached
+        return Err(IsentropicFlowError::MathError.into());
+    }
+    Ok(cp0 / denominator)
+}
+
+pub fn calc_critical_pressure_coefficient(mach_number: f64, specific_heat_ratio: f64) -> Result<f64, CompressibleFlowError> {
+    // Cp* = 2 / (γ M²) ((p*/p0) / (p∞/p0) - 1), the pressure coefficient where the local flow turns sonic
+    if mach_number <= 0.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    let sonic_pressure_ratio: f64 = isentropic::calc_pressure_ratio_from_mach(1.0, specific_heat_ratio)?;
+    let freestream_pressure_ratio: f64 = isentropic::calc_pressure_ratio_from_mach(mach_number, specific_heat_ratio)?;
+    Ok(2.0 / (specific_heat_ratio * mach_number.powi(2)) * (sonic_pressure_ratio / freestream_pressure_ratio - 1.0))
+}
+
+pub fn calc_critical_mach(min_incompressible_pressure_coefficient: f64, correction: CompressibilityCorrection, specific_heat_ratio: f64) -> Result<f64, CompressibleFlowError> {
+    // freestream mach number where the corrected minimum pressure coefficient
+    // first reaches Cp*, found by stepping up from low speed to bracket the
+    // root before a singular correction can get in the way
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    if min_incompressible_pressure_coefficient >= 0.0 {
+        return Err(CompressibleFlowError::InvalidPressureCoefficient);
+    }
+    let f = |mach_number: f64| {
+        match (
+            calc_compressible_pressure_coefficient(min_incompressible_pressure_coefficient, mach_number, correction, specific_heat_ratio),
+            calc_critical_pressure_coefficient(mach_number, specific_heat_ratio),
+        ) {
+            (Ok(pressure_coefficient), Ok(critical_pressure_coefficient)) => pressure_coefficient - critical_pressure_coefficient,
+            // past a singular correction the flow is already critical
+            _ => f64::NEG_INFINITY,
+        }
+    };
+
+    let step: f64 = 0.01;
+    let mut lower_mach: f64 = step;
+    while lower_mach < 1.0 {
+        let upper_mach: f64 = (lower_mach + step).min(1.0 - 1e-9);
+        if f(upper_mach) <= 0.0 {
+            return Ok(bisection(&f, lower_mach, upper_mach, Some(1e-10), None));
+        }
+        lower_mach = upper_mach;
+    }
+    Err(IsentropicFlowError::MathError.into())
+}
@@ -0,0 +1,130 @@
+use crate::isentropic::{valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::normalshock::NormalShock;
+
+
+// one gamma model of a reactive wave, heat_release is the nondimensional
+// chemical energy q / (R T1) added between the upstream and downstream states,
+// wave mach numbers are the wave speed over the upstream speed of sound
+
+#[derive(Debug, Clone, Copy)]
+pub enum DetonationBranch {
+    Strong, // further from the initial state, subsonic downstream
+    Weak,   // closer to the initial state, supersonic behind a detonation
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DetonationState {
+    pub wave_mach: f64,         // M1 = D / a1
+    pub pressure_ratio: f64,    // p2 / p1
+    pub density_ratio: f64,     // ρ2 / ρ1
+    pub temperature_ratio: f64, // T2 / T1
+    pub velocity_ratio: f64,    // u2 / u1 in the wave frame
+    pub downstream_mach: f64,   // M2 in the wave frame, 1 at a chapman jouguet point
+}
+
+pub fn calc_chapman_jouguet_mach(heat_release: f64, specific_heat_ratio: f64, detonation: bool) -> Result<f64, CompressibleFlowError> {
+    // MCJ = √(H + 1) ± √H, H = (γ² - 1) Q / (2 γ), upper sign for the
+    // detonation and lower sign for the deflagration
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    if heat_release < 0.0 {
+        return Err(IsentropicFlowError::InvalidTemperatureRatio.into());
+    }
+    let h: f64 = (specific_heat_ratio.powi(2) - 1.0) * heat_release / (2.0 * specific_heat_ratio);
+    if detonation {
+        Ok((h + 1.0).sqrt() + h.sqrt())
+    } else {
+        Ok((h + 1.0).sqrt() - h.sqrt())
+    }
+}
+
+pub fn calc_chapman_jouguet_state(heat_release: f64, specific_heat_ratio: f64, detonation: bool) -> Result<DetonationState, CompressibleFlowError> {
+    // both branches coincide at the tangency of the rayleigh line and the hugoniot
+    let wave_mach: f64 = calc_chapman_jouguet_mach(heat_release, specific_heat_ratio, detonation)?;
+    calc_state(wave_mach, heat_release, DetonationBranch::Strong, specific_heat_ratio)
+}
+
+pub fn calc_detonation_state(wave_mach: f64, heat_release: f64, branch: DetonationBranch, specific_heat_ratio: f64) -> Result<DetonationState, CompressibleFlowError> {
+    // downstream state of a detonation (M1 > MCJ+) or deflagration (M1 < MCJ-)
+    // on the chosen branch, no steady solution exists between the two
+    calc_state(wave_mach, heat_release, branch, specific_heat_ratio)
+}
+
+pub fn calc_overdriven_detonation_state(overdrive: f64, heat_release: f64, branch: DetonationBranch, specific_heat_ratio: f64) -> Result<DetonationState, CompressibleFlowError> {
+    // overdrive f = (D / DCJ)², f = 1 is the chapman jouguet detonation
+    if overdrive < 1.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    let chapman_jouguet_mach: f64 = calc_chapman_jouguet_mach(heat_release, specific_heat_ratio, true)?;
+    calc_state(chapman_jouguet_mach * overdrive.sqrt(), heat_release, branch, specific_heat_ratio)
+}
+
+pub fn calc_von_neumann_state(wave_mach: f64, specific_heat_ratio: f64) -> Result<DetonationState, CompressibleFlowError> {
+    // the leading shock of the detonation before any heat is released
+    if wave_mach <= 1.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    let normal_shock: NormalShock = NormalShock::from_upstream_mach(wave_mach, specific_heat_ratio)?;
+    Ok(DetonationState {
+        wave_mach,
+        pressure_ratio: normal_shock.pressure_ratio,
+        density_ratio: normal_shock.density_ratio,
+        temperature_ratio: normal_shock.temperature_ratio,
+        velocity_ratio: normal_shock.velocity_ratio,
+        downstream_mach: normal_shock.downstream_mach,
+    })
+}
+
+pub(crate) fn calc_state(wave_mach: f64, heat_release: f64, branch: DetonationBranch, specific_heat_ratio: f64) -> Result<DetonationState, CompressibleFlowError> {
+    // the rayleigh line p = 1 + γ M1² (1 - v) substituted into the reactive
+    // hugoniot γ / (γ - 1) (p v - 1) - Q = (p - 1) (1 + v) / 2 leaves a
+    // quadratic in v = v2 / v1, its discriminant vanishes at the cj points
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    if heat_release < 0.0 {
+        return Err(IsentropicFlowError::InvalidTemperatureRatio.into());
+    }
+    if wave_mach <= 0.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    let k: f64 = specific_heat_ratio / (specific_heat_ratio - 1.0);
+    let m: f64 = specific_heat_ratio * wave_mach.powi(2);
+    let a: f64 = m * (0.5 - k);
+    let b: f64 = k * (1.0 + m);
+    let c: f64 = -(k + heat_release + m / 2.0);
+
+    let mut discriminant: f64 = b.powi(2) - 4.0 * a * c;
+    if discriminant.abs() < 1e-12 * b.powi(2) {
+        // rounding leaves the cj discriminant a hair either side of zero
+        discriminant = 0.0;
+    }
+    if discriminant < 0.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    // a < 0, so these are the larger and smaller volume ratios
+    let larger_volume_ratio: f64 = (-b - discriminant.sqrt()) / (2.0 * a);
+    let smaller_volume_ratio: f64 = (-b + discriminant.sqrt()) / (2.0 * a);
+
+    // the strong branch is the one further from v = 1
+    let volume_ratio: f64 = match (branch, wave_mach > 1.0) {
+        (DetonationBranch::Strong, true) | (DetonationBranch::Weak, false) => smaller_volume_ratio,
+        (DetonationBranch::Weak, true) | (DetonationBranch::Strong, false) => larger_volume_ratio,
+    };
+    if volume_ratio <= 0.0 {
+        return Err(IsentropicFlowError::MathError.into());
+    }
+
+    let pressure_ratio: f64 = 1.0 + m * (1.0 - volume_ratio);
+    let temperature_ratio: f64 = pressure_ratio * volume_ratio;
+    Ok(DetonationState {
+        wave_mach,
+        pressure_ratio,
+        density_ratio: 1.0 / volume_ratio,
+        temperature_ratio,
+        velocity_ratio: volume_ratio,
+        downstream_mach: wave_mach * volume_ratio / temperature_ratio.sqrt(),
+    })
+}
@@ -0,0 +1,126 @@
+use crate::isentropic::{valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::normalshock::NormalShock;
+use crate::gas::{GasModel, UNIVERSAL_GAS_CONSTANT};
+use crate::numerics::bisection;
+
+
+// lighthill freeman ideal dissociating gas, with α the mass fraction of atoms
+// and R the gas constant of the molecules
+//     p = ρ R (1 + α) T
+//     h = R ((4 + α) T + α θd)
+// so the molecules carry half excited vibration, cp = 4 R when α = 0
+
+#[derive(Debug)]
+pub struct EquilibriumNormalShock {
+    pub upstream_dissociation_fraction: f64, // α1 in equilibrium at T1, p1
+    pub dissociation_fraction: f64,          // α2
+    pub temperature: f64,                    // T2, K
+    pub pressure: f64,                       // p2, Pa
+    pub density: f64,                        // ρ2, kg/m³
+    pub velocity: f64,                       // u2 relative to the shock, m/s
+    pub pressure_ratio: f64,                 // p2 / p1
+    pub density_ratio: f64,                  // ρ2 / ρ1
+    pub temperature_ratio: f64,              // T2 / T1
+}
+
+impl EquilibriumNormalShock {
+    pub fn new(gas: GasModel, upstream_velocity: f64, upstream_temperature: f64, upstream_pressure: f64) -> Result<EquilibriumNormalShock, CompressibleFlowError> {
+        // upstream velocity relative to the shock, the calorically perfect model
+        // reduces to NormalShock::from_upstream_mach
+        if upstream_velocity <= 0.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        if upstream_temperature <= 0.0 {
+            return Err(IsentropicFlowError::InvalidTemperatureRatio.into());
+        }
+        if upstream_pressure <= 0.0 {
+            return Err(IsentropicFlowError::InvalidPressureRatio.into());
+        }
+        match gas {
+            GasModel::CaloricallyPerfect { molar_mass, specific_heat_ratio } => {
+                if !valid_specific_heat_ratio(specific_heat_ratio) {
+                    return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+                }
+                let r: f64 = UNIVERSAL_GAS_CONSTANT / molar_mass;
+                let upstream_mach: f64 = upstream_velocity / (specific_heat_ratio * r * upstream_temperature).sqrt();
+                let normal_shock: NormalShock = NormalShock::from_upstream_mach(upstream_mach, specific_heat_ratio)?;
+                let upstream_density: f64 = upstream_pressure / (r * upstream_temperature);
+                Ok(EquilibriumNormalShock {
+                    upstream_dissociation_fraction: 0.0,
+                    dissociation_fraction: 0.0,
+                    temperature: upstream_temperature * normal_shock.temperature_ratio,
+                    pressure: upstream_pressure * normal_shock.pressure_ratio,
+                    density: upstream_density * normal_shock.density_ratio,
+                    velocity: upstream_velocity * normal_shock.velocity_ratio,
+                    pressure_ratio: normal_shock.pressure_ratio,
+                    density_ratio: normal_shock.density_ratio,
+                    temperature_ratio: normal_shock.temperature_ratio,
+                })
+            }
+            GasModel::IdealDissociating(species) => {
+                let r: f64 = species.gas_constant();
+                let dissociation_temperature: f64 = species.dissociation_temperature;
+
+                // at fixed p and T the equilibrium gives α² / (1 - α²) = (ρd R T / p) exp(-θd / T)
+                let c: f64 = species.characteristic_density * r * upstream_temperature / upstream_pressure * (-dissociation_temperature / upstream_temperature).exp();
+                let upstream_dissociation_fraction: f64 = (c / (1.0 + c)).sqrt();
+                let upstream_density: f64 = upstream_pressure / (r * (1.0 + upstream_dissociation_fraction) * upstream_temperature);
+                let upstream_enthalpy: f64 = r * ((4.0 + upstream_dissociation_fraction) * upstream_temperature + upstream_dissociation_fraction * dissociation_temperature);
+                let total_enthalpy: f64 = upstream_enthalpy + upstream_velocity.powi(2) / 2.0;
+
+                // for a fixed α2 the jump conditions leave a quadratic in ε = ρ1 / ρ2
+                //     (k - 1/2) u1² ε² - k (p1 / ρ1 + u1²) ε + H - α2 R θd = 0, k = (4 + α2) / (1 + α2)
+                // the smaller root is the shock
+                let jump = |dissociation_fraction: f64| -> Result<(f64, f64), CompressibleFlowError> {
+                    let k: f64 = (4.0 + dissociation_fraction) / (1.0 + dissociation_fraction);
+                    let a: f64 = (k - 0.5) * upstream_velocity.powi(2);
+                    let b: f64 = k * (upstream_pressure / upstream_density + upstream_velocity.powi(2));
+                    let c: f64 = total_enthalpy - dissociation_fraction * r * dissociation_temperature;
+                    let discriminant: f64 = b.powi(2) - 4.0 * a * c;
+                    if discriminant < 0.0 {
+                        return Err(IsentropicFlowError::InvalidMachNumber.into());
+                    }
+                    let volume_ratio: f64 = (b - discriminant.sqrt()) / (2.0 * a);
+                    let temperature: f64 = volume_ratio * (upstream_pressure / upstream_density + upstream_velocity.powi(2) * (1.0 - volume_ratio)) / (r * (1.0 + dissociation_fraction));
+                    Ok((volume_ratio, temperature))
+                };
+
+                // the frozen shock with α2 = α1 has to compress the gas at all
+                let (frozen_volume_ratio, _) = jump(upstream_dissociation_fraction)?;
+                if frozen_volume_ratio >= 1.0 {
+                    return Err(IsentropicFlowError::InvalidMachNumber.into());
+                }
+
+                // equilibrium residual, negative at α1 and positive once the
+                // dissociation energy has used up the whole total enthalpy
+                let g = |dissociation_fraction: f64| match jump(dissociation_fraction) {
+                    Ok((volume_ratio, temperature)) => {
+                        dissociation_fraction - species.equilibrium_dissociation_fraction(upstream_density / volume_ratio, temperature)
+                    }
+                    Err(_) => f64::NAN,
+                };
+                let upper: f64 = (1.0_f64 - 1e-12).min(total_enthalpy / (r * dissociation_temperature) * (1.0 - 1e-12));
+                let dissociation_fraction: f64 = if g(upper) <= 0.0 {
+                    upper
+                } else {
+                    bisection(&g, upstream_dissociation_fraction, upper, Some(1e-12), None)
+                };
+                let (volume_ratio, temperature) = jump(dissociation_fraction)?;
+                let pressure: f64 = upstream_pressure + upstream_density * upstream_velocity.powi(2) * (1.0 - volume_ratio);
+
+                Ok(EquilibriumNormalShock {
+                    upstream_dissociation_fraction,
+                    dissociation_fraction,
+                    temperature,
+                    pressure,
+                    density: upstream_density / volume_ratio,
+                    velocity: upstream_velocity * volume_ratio,
+                    pressure_ratio: pressure / upstream_pressure,
+                    density_ratio: 1.0 / volume_ratio,
+                    temperature_ratio: temperature / upstream_temperature,
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::isentropic::IsentropicFlowError;
+
+
+// errors from the modules built on top of the gas dynamic relations, a failure
+// inside isentropic, normal shock, oblique shock or cone flow is passed on as Flow
+#[derive(Debug)]
+pub enum CompressibleFlowError {
+    Flow(IsentropicFlowError),
+    InvalidGridPoints,
+    InvalidNumberOfCharacteristics,
+    InvalidGeometry,
+    InvalidPressureCoefficient,
+    InvalidAltitude,
+    InvalidComposition,
+    InvalidGas,
+    InvalidFlowRegime,
+    InvalidMesh,
+    DetachedShock,
+}
+
+impl From<IsentropicFlowError> for CompressibleFlowError {
+    fn from(error: IsentropicFlowError) -> CompressibleFlowError {
+        CompressibleFlowError::Flow(error)
+    }
+}
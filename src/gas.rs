@@ -0,0 +1,257 @@
+use crate::error::CompressibleFlowError;
+
+
+// species data for the real gas models, SI units
+pub const UNIVERSAL_GAS_CONSTANT: f64 = 8.314462618; // J/(mol K)
+
+#[derive(Debug, Clone, Copy)]
+pub struct DiatomicGas {
+    pub molar_mass: f64,               // kg/mol of the molecule
+    pub vibrational_temperature: f64,  // θv, K
+    pub dissociation_temperature: f64, // θd = D / k, K
+    pub characteristic_density: f64,   // ρd of the lighthill freeman model, kg/m³
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GasProperties {
+    pub name: &'static str,
+    pub formula: &'static str,
+    pub molar_mass: f64,             // kg/mol
+    pub specific_heat_ratio: f64,    // γ at 300 K
+    pub reference_viscosity: f64,    // sutherland μ0 at T0, Pa s
+    pub reference_temperature: f64,  // sutherland T0, K
+    pub sutherland_temperature: f64, // sutherland S, K
+    pub critical_temperature: f64,   // K
+    pub critical_pressure: f64,      // Pa
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum HeatCapacity {
+    Constant(f64),        // cp / Ru, calorically perfect
+    Polynomial([f64; 5]), // cp / Ru = a1 + a2 T + a3 T² + a4 T³ + a5 T⁴, thermally perfect
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Species {
+    pub molar_mass: f64,             // kg/mol
+    pub heat_capacity: HeatCapacity,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum GasModel {
+    CaloricallyPerfect { molar_mass: f64, specific_heat_ratio: f64 },
+    IdealDissociating(DiatomicGas), // lighthill freeman, A2 ⇌ 2A in equilibrium
+}
+
+pub const NITROGEN: DiatomicGas = DiatomicGas {
+    molar_mass: 28.0134e-3,
+    vibrational_temperature: 3393.0,
+    dissociation_temperature: 113000.0,
+    characteristic_density: 130000.0,
+};
+
+pub const OXYGEN: DiatomicGas = DiatomicGas {
+    molar_mass: 31.9988e-3,
+    vibrational_temperature: 2273.0,
+    dissociation_temperature: 59500.0,
+    characteristic_density: 150000.0,
+};
+
+pub const AIR: GasProperties = GasProperties {
+    name: "air",
+    formula: "air",
+    molar_mass: 28.9647e-3,
+    specific_heat_ratio: 1.4,
+    reference_viscosity: 1.716e-5,
+    reference_temperature: 273.15,
+    sutherland_temperature: 110.4,
+    critical_temperature: 132.5,
+    critical_pressure: 3.77e6,
+};
+
+pub const GASES: [GasProperties; 10] = [
+    AIR,
+    GasProperties {
+        name: "nitrogen",
+        formula: "n2",
+        molar_mass: 28.0134e-3,
+        specific_heat_ratio: 1.4,
+        reference_viscosity: 1.663e-5,
+        reference_temperature: 273.15,
+        sutherland_temperature: 107.0,
+        critical_temperature: 126.19,
+        critical_pressure: 3.3958e6,
+    },
+    GasProperties {
+        name: "oxygen",
+        formula: "o2",
+        molar_mass: 31.9988e-3,
+        specific_heat_ratio: 1.395,
+        reference_viscosity: 1.919e-5,
+        reference_temperature: 273.15,
+        sutherland_temperature: 139.0,
+        critical_temperature: 154.58,
+        critical_pressure: 5.043e6,
+    },
+    GasProperties {
+        name: "helium",
+        formula: "he",
+        molar_mass: 4.0026e-3,
+        specific_heat_ratio: 5.0 / 3.0,
+        reference_viscosity: 1.87e-5,
+        reference_temperature: 273.15,
+        sutherland_temperature: 79.4,
+        critical_temperature: 5.195,
+        critical_pressure: 0.2275e6,
+    },
+    GasProperties {
+        name: "argon",
+        formula: "ar",
+        molar_mass: 39.948e-3,
+        specific_heat_ratio: 5.0 / 3.0,
+        reference_viscosity: 2.125e-5,
+        reference_temperature: 273.15,
+        sutherland_temperature: 144.0,
+        critical_temperature: 150.69,
+        critical_pressure: 4.863e6,
+    },
+    GasProperties {
+        name: "hydrogen",
+        formula: "h2",
+        molar_mass: 2.01588e-3,
+        specific_heat_ratio: 1.405,
+        reference_viscosity: 8.411e-6,
+        reference_temperature: 273.15,
+        sutherland_temperature: 97.0,
+        critical_temperature: 33.145,
+        critical_pressure: 1.2964e6,
+    },
+    GasProperties {
+        name: "carbon dioxide",
+        formula: "co2",
+        molar_mass: 44.0095e-3,
+        specific_heat_ratio: 1.289,
+        reference_viscosity: 1.370e-5,
+        reference_temperature: 273.15,
+        sutherland_temperature: 222.0,
+        critical_temperature: 304.13,
+        critical_pressure: 7.3773e6,
+    },
+    GasProperties {
+        name: "methane",
+        formula: "ch4",
+        molar_mass: 16.0425e-3,
+        specific_heat_ratio: 1.304,
+        reference_viscosity: 1.03e-5,
+        reference_temperature: 273.15,
+        sutherland_temperature: 164.0,
+        critical_temperature: 190.56,
+        critical_pressure: 4.599e6,
+    },
+    GasProperties {
+        name: "steam",
+        formula: "h2o",
+        molar_mass: 18.0153e-3,
+        specific_heat_ratio: 1.33,
+        reference_viscosity: 1.12e-5,
+        reference_temperature: 350.0,
+        sutherland_temperature: 1064.0,
+        critical_temperature: 647.096,
+        critical_pressure: 22.064e6,
+    },
+    // stoichiometric methane air burnt to 71.5 % N2, 9.5 % CO2 and 19 % H2O by
+    // mole, viscosity as air and pseudo critical point by kay's rule
+    GasProperties {
+        name: "combustion products",
+        formula: "products",
+        molar_mass: 27.63e-3,
+        specific_heat_ratio: 1.371,
+        reference_viscosity: 1.716e-5,
+        reference_temperature: 273.15,
+        sutherland_temperature: 110.4,
+        critical_temperature: 242.1,
+        critical_pressure: 7.32e6,
+    },
+];
+
+pub fn from_name(name: &str) -> Result<GasProperties, CompressibleFlowError> {
+    // by name or formula, ignoring case, "carbon_dioxide" and "Carbon-Dioxide"
+    // both find carbon dioxide
+    let name: String = name.trim().to_lowercase().replace(['_', '-'], " ");
+    GASES.iter()
+        .find(|gas| gas.name == name || gas.formula == name || (name == "water" && gas.formula == "h2o"))
+        .copied()
+        .ok_or(CompressibleFlowError::InvalidGas)
+}
+
+pub fn specific_heat_ratio(name: &str) -> Result<f64, CompressibleFlowError> {
+    // γ at 300 K by name, for anything that takes specific_heat_ratio
+    Ok(from_name(name)?.specific_heat_ratio)
+}
+
+impl GasProperties {
+    pub fn gas_constant(&self) -> f64 {
+        UNIVERSAL_GAS_CONSTANT / self.molar_mass
+    }
+
+    pub fn viscosity(&self, temperature: f64) -> f64 {
+        // sutherland μ = μ0 (T / T0)^3/2 (T0 + S) / (T + S)
+        self.reference_viscosity * (temperature / self.reference_temperature).powf(1.5)
+            * (self.reference_temperature + self.sutherland_temperature) / (temperature + self.sutherland_temperature)
+    }
+
+    pub fn species(&self) -> Species {
+        // calorically perfect at its 300 K γ, for building mixtures
+        Species {
+            molar_mass: self.molar_mass,
+            heat_capacity: HeatCapacity::Constant(self.specific_heat_ratio / (self.specific_heat_ratio - 1.0)),
+        }
+    }
+}
+
+impl Species {
+    pub fn molar_heat_capacity(&self, temperature: f64) -> f64 {
+        // cp / Ru
+        match self.heat_capacity {
+            HeatCapacity::Constant(heat_capacity) => heat_capacity,
+            HeatCapacity::Polynomial(a) => a.iter().rev().fold(0.0, |sum, coefficient| sum * temperature + coefficient),
+        }
+    }
+
+    pub fn specific_heat_ratio(&self, temperature: f64) -> f64 {
+        let heat_capacity: f64 = self.molar_heat_capacity(temperature);
+        heat_capacity / (heat_capacity - 1.0)
+    }
+}
+
+impl DiatomicGas {
+    pub fn gas_constant(&self) -> f64 {
+        // specific gas constant, J/(kg K)
+        UNIVERSAL_GAS_CONSTANT / self.molar_mass
+    }
+
+    pub fn vibrational_energy(&self, temperature: f64) -> f64 {
+        // harmonic oscillator ev = R θv / (exp(θv / T) - 1), J/kg
+        if temperature <= 0.0 {
+            return 0.0;
+        }
+        self.gas_constant() * self.vibrational_temperature / (self.vibrational_temperature / temperature).exp_m1()
+    }
+
+    pub fn vibrational_temperature_from_energy(&self, vibrational_energy: f64) -> f64 {
+        // Tv with ev(Tv) equal to the given energy
+        if vibrational_energy <= 0.0 {
+            return 0.0;
+        }
+        self.vibrational_temperature / (self.gas_constant() * self.vibrational_temperature / vibrational_energy).ln_1p()
+    }
+
+    pub fn equilibrium_dissociation_fraction(&self, density: f64, temperature: f64) -> f64 {
+        // α² / (1 - α) = (ρd / ρ) exp(-θd / T)
+        if temperature <= 0.0 {
+            return 0.0;
+        }
+        let c: f64 = self.characteristic_density / density * (-self.dissociation_temperature / temperature).exp();
+        2.0 / (1.0 + (1.0 + 4.0 / c).sqrt())
+    }
+}
@@ -0,0 +1,86 @@
+use crate::isentropic::{valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::normalshock::NormalShock;
+
+
+// p - v diagram curves through the upstream state, all nondimensionalised
+// by it so every curve passes through (v / v1, p / p1) = (1, 1)
+
+pub fn calc_hugoniot_pressure_ratio(volume_ratio: f64, specific_heat_ratio: f64) -> Result<f64, CompressibleFlowError> {
+    // p2 / p1 = ((γ + 1) - (γ - 1) v2 / v1) / ((γ + 1) v2 / v1 - (γ - 1)), the
+    // curve is asymptotic to the maximum compression v2 / v1 = (γ - 1) / (γ + 1)
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    if volume_ratio <= (specific_heat_ratio - 1.0) / (specific_heat_ratio + 1.0) {
+        return Err(IsentropicFlowError::InvalidDensityRatio.into());
+    }
+    let pressure_ratio: f64 = 
+        ((specific_heat_ratio + 1.0) - (specific_heat_ratio - 1.0) * volume_ratio) /
+        ((specific_heat_ratio + 1.0) * volume_ratio - (specific_heat_ratio - 1.0));
+    Ok(pressure_ratio)
+}
+
+pub fn calc_rayleigh_line_pressure_ratio(volume_ratio: f64, upstream_mach: f64, specific_heat_ratio: f64) -> Result<f64, CompressibleFlowError> {
+    // momentum across the wave, p2 / p1 = 1 + γ M1² (1 - v2 / v1) where M1 is
+    // the shock speed relative to the upstream gas over its speed of sound
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    if upstream_mach <= 0.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    if volume_ratio <= 0.0 {
+        return Err(IsentropicFlowError::InvalidDensityRatio.into());
+    }
+    Ok(1.0 + specific_heat_ratio * upstream_mach.powi(2) * (1.0 - volume_ratio))
+}
+
+pub fn calc_isentrope_pressure_ratio(volume_ratio: f64, specific_heat_ratio: f64) -> Result<f64, CompressibleFlowError> {
+    // p2 / p1 = (v2 / v1)^-γ
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    if volume_ratio <= 0.0 {
+        return Err(IsentropicFlowError::InvalidDensityRatio.into());
+    }
+    Ok(volume_ratio.powf(-specific_heat_ratio))
+}
+
+pub fn calc_hugoniot_curve(min_volume_ratio: f64, max_volume_ratio: f64, points: usize, specific_heat_ratio: f64) -> Result<Vec<(f64, f64)>, CompressibleFlowError> {
+    // (v2 / v1, p2 / p1) from min to max volume ratio
+    sample_curve(min_volume_ratio, max_volume_ratio, points, |volume_ratio| calc_hugoniot_pressure_ratio(volume_ratio, specific_heat_ratio))
+}
+
+pub fn calc_rayleigh_line(upstream_mach: f64, min_volume_ratio: f64, max_volume_ratio: f64, points: usize, specific_heat_ratio: f64) -> Result<Vec<(f64, f64)>, CompressibleFlowError> {
+    sample_curve(min_volume_ratio, max_volume_ratio, points, |volume_ratio| calc_rayleigh_line_pressure_ratio(volume_ratio, upstream_mach, specific_heat_ratio))
+}
+
+pub fn calc_isentrope(min_volume_ratio: f64, max_volume_ratio: f64, points: usize, specific_heat_ratio: f64) -> Result<Vec<(f64, f64)>, CompressibleFlowError> {
+    sample_curve(min_volume_ratio, max_volume_ratio, points, |volume_ratio| calc_isentrope_pressure_ratio(volume_ratio, specific_heat_ratio))
+}
+
+pub fn calc_shock_state(upstream_mach: f64, specific_heat_ratio: f64) -> Result<(f64, f64), CompressibleFlowError> {
+    // (v2 / v1, p2 / p1) behind a normal shock, where the rayleigh line for
+    // this shock speed meets the hugoniot
+    if upstream_mach < 1.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    let normal_shock: NormalShock = NormalShock::from_upstream_mach(upstream_mach, specific_heat_ratio)?;
+    Ok((1.0 / normal_shock.density_ratio, normal_shock.pressure_ratio))
+}
+
+fn sample_curve(min_volume_ratio: f64, max_volume_ratio: f64, points: usize, f: impl Fn(f64) -> Result<f64, CompressibleFlowError>) -> Result<Vec<(f64, f64)>, CompressibleFlowError> {
+    if points < 2 {
+        return Err(CompressibleFlowError::InvalidGridPoints);
+    }
+    if min_volume_ratio >= max_volume_ratio {
+        return Err(IsentropicFlowError::InvalidDensityRatio.into());
+    }
+    (0..points)
+        .map(|i| {
+            let volume_ratio = min_volume_ratio + (max_volume_ratio - min_volume_ratio) * i as f64 / (points - 1) as f64;
+            Ok((volume_ratio, f(volume_ratio)?))
+        })
+        .collect()
+}
@@ -1,5 +1,6 @@
 use std::f64::consts::PI;
 use crate::numerics::*;
+use crate::gas::GasProperties;
 
 
 #[derive(Debug)]
@@ -11,6 +12,7 @@ pub enum IsentropicFlowError {
     InvalidDensityRatio,
     InvalidPrandtlMeyerAngle,
     InvalidSpecificHeatRatio,
+    InvalidAreaRatio,
     WhatTheFuck,
     MathError,
 }
@@ -71,7 +73,7 @@ impl IsentropicFlow {
 
 pub fn calculate(output: Output, input: Input, specific_heat_ratio: Option<f64>) -> Result<f64, IsentropicFlowError> {
     // simple calculator function for if you're lazy 
-    let specific_heat_ratio = specific_heat_ratio.unwrap_or(1.4);
+    let specific_heat_ratio = specific_heat_ratio.unwrap_or(crate::gas::AIR.specific_heat_ratio);
     if !valid_specific_heat_ratio(specific_heat_ratio) {
         return Err(IsentropicFlowError::InvalidSpecificHeatRatio);
     }
@@ -112,6 +114,11 @@ pub fn calculate(output: Output, input: Input, specific_heat_ratio: Option<f64>)
     }
 }
 
+pub fn calculate_for_gas(output: Output, input: Input, gas: GasProperties) -> Result<f64, IsentropicFlowError> {
+    // same as calculate with γ taken from a catalogue gas, gas::from_name("helium")
+    calculate(output, input, Some(gas.specific_heat_ratio))
+}
+
 pub fn calc_mach_angle_from_mach(mach_number: f64) -> Result<f64, IsentropicFlowError> {
     if mach_number < 0.0 {
         return Err(IsentropicFlowError::InvalidMachNumber);
@@ -144,6 +151,21 @@ pub fn calc_density_ratio_from_mach(mach_number: f64, specific_heat_ratio: f64)
     Ok(density_ratio)
 }
 
+pub fn calc_area_ratio_from_mach(mach_number: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
+    // area-mach relation, returns A / A* for the given mach number
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio);
+    }
+    if mach_number <= 0.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber);
+    }
+    let area_ratio: f64 = 
+        (1.0 / mach_number) * 
+        ((2.0 / (specific_heat_ratio + 1.0)) * (1.0 + (specific_heat_ratio - 1.0) / 2.0 * mach_number.powi(2)))
+        .powf((specific_heat_ratio + 1.0) / (2.0 * (specific_heat_ratio - 1.0)));
+    Ok(area_ratio)
+}
+
 pub fn calc_mach_from_speed_of_sound(velocity: f64, speed_of_sound: f64) -> Result<f64, IsentropicFlowError> {
     Ok(velocity / speed_of_sound)
 }
@@ -165,7 +187,7 @@ pub fn prandtl_meyer_function(mach_number: f64, specific_heat_ratio: f64) -> Res
 }
 
 pub fn calc_mach_from_mach_angle(mach_angle: f64) -> Result<f64, IsentropicFlowError> {
-    if mach_angle < 0.0 || mach_angle > PI / 2.0 {
+    if !(0.0..=PI / 2.0).contains(&mach_angle) {
         // check valid mach angle in radians
         return Err(IsentropicFlowError::InvalidMachAngle)
     }
@@ -209,12 +231,52 @@ pub fn calc_mach_from_density_ratio(density_ratio: f64, specific_heat_ratio: f64
     Ok(mach_number)
 }
 
+pub fn calc_mach_from_area_ratio(area_ratio: f64, specific_heat_ratio: f64, supersonic: bool) -> Result<f64, IsentropicFlowError> {
+    // the area-mach relation is double valued so the branch has to be picked
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio);
+    }
+    if area_ratio < 1.0 {
+        return Err(IsentropicFlowError::InvalidAreaRatio);
+    }
+    let f = |mach_number: f64| {
+        match calc_area_ratio_from_mach(mach_number, specific_heat_ratio) {
+            Ok(value) => value - area_ratio,
+            Err(_) => f64::NAN,
+        }
+    };
+    let (lower_bound, upper_bound) = if supersonic { (1.0, 100.0) } else { (1e-6, 1.0) };
+    // the root has to be inside the bracket, past it bisection stops at an end
+    let (f_lower, f_upper) = (f(lower_bound), f(upper_bound));
+    if !f_lower.is_finite() || !f_upper.is_finite() || f_lower * f_upper > 0.0 {
+        return Err(IsentropicFlowError::InvalidAreaRatio);
+    }
+    let mach_number: f64 = bisection(&f, lower_bound, upper_bound, Some(1e-12), None);
+    Ok(mach_number)
+}
+
 pub fn calc_mach_from_prandtl_meyer_angle(prandtl_meyer_angle: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
     // eta is the value of (m^2 - 1).sqrt()
     if !valid_specific_heat_ratio(specific_heat_ratio) {
         return Err(IsentropicFlowError::InvalidSpecificHeatRatio);
     }
     let alpha = ((specific_heat_ratio + 1.0) / (specific_heat_ratio - 1.0)).sqrt(); // just a constant to make things easier
+    if !(0.0..PI / 2.0 * (alpha - 1.0)).contains(&prandtl_meyer_angle) {
+        return Err(IsentropicFlowError::InvalidPrandtlMeyerAngle);
+    }
+
+    // small angle expansion ν ≈ (1 - 1/α²) η³ / 3 and large angle expansion
+    // ν ≈ νmax - (α² - 1) / η, the larger one is the initial guess, the
+    // derivative vanishes at both ends so a fixed guess fails there
+    let small_guess: f64 = (3.0 * prandtl_meyer_angle / (1.0 - 1.0 / alpha.powi(2))).cbrt();
+    let large_guess: f64 = (alpha.powi(2) - 1.0) / (PI / 2.0 * (alpha - 1.0) - prandtl_meyer_angle);
+    if small_guess < 1e-6 {
+        return Ok((small_guess.powi(2) + 1.0).sqrt());
+    }
+    if large_guess > 1e4 {
+        return Ok((large_guess.powi(2) + 1.0).sqrt());
+    }
+    let eta_guess: f64 = small_guess.max(large_guess);
     let f = |eta: f64| {
         alpha * (eta / alpha).atan()
         - eta.atan() - prandtl_meyer_angle    
@@ -223,7 +285,7 @@ pub fn calc_mach_from_prandtl_meyer_angle(prandtl_meyer_angle: f64, specific_hea
         1.0 / ((eta / alpha).powi(2) + 1.0)
         - 1.0 / (eta.powi(2) + 1.0)
     };
-    let eta: f64 = newton_raphson(&f, &df, 1.5, None, None);
+    let eta: f64 = newton_raphson(&f, &df, eta_guess, Some(1e-10 * eta_guess.max(1.0)), None).abs();
     let mach_number: f64 = (eta.powi(2) + 1.0).sqrt();
     Ok(mach_number)
 }
@@ -3,4 +3,28 @@ pub mod normalshock;
 pub mod obliqueshock;
 pub mod taylormaccoll;
 pub mod numerics;
+pub mod error;
+pub mod nozzleflow;
+pub mod characteristics;
+pub mod bellnozzle;
+pub mod shockexpansion;
+pub mod thinairfoil;
+pub mod compressibility;
+pub mod pitot;
+pub mod airdata;
+pub mod hugoniot;
+pub mod detonation;
+pub mod znd;
+pub mod gas;
+pub mod relaxation;
+pub mod dissociation;
+pub mod mixture;
+pub mod liquid;
+pub mod realgas;
+pub mod boundarylayer;
+pub mod similarity;
+pub mod stagnationheating;
+pub mod bluntbody;
+pub mod newtonian;
+pub mod tangentmethod;
 pub mod tests;
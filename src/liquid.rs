@@ -0,0 +1,336 @@
+use crate::isentropic::{valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::numerics::bisection;
+
+
+// liquids as a shifted perfect gas, both models share the isentrope
+// (p + π) ∝ ρ^γ and the speed of sound c² = γ (p + π) / ρ with π = p∞ or B
+//     stiffened gas   p = (γ - 1) ρ e - γ p∞, with the full rankine hugoniot
+//     tait            p = (pref + B) (ρ / ρref)^n - B, barotropic so shocks only
+//                     conserve mass and momentum and follow the isentrope
+// pressures are absolute in Pa, velocities in m/s and shocks run into
+// liquid at rest
+
+#[derive(Debug, Clone, Copy)]
+pub enum LiquidModel {
+    StiffenedGas { specific_heat_ratio: f64, stiffness_pressure: f64 }, // γ, p∞
+    Tait { exponent: f64, bulk_modulus: f64 },                          // n, B
+}
+
+pub const WATER_STIFFENED_GAS: LiquidModel = LiquidModel::StiffenedGas { specific_heat_ratio: 4.4, stiffness_pressure: 6.0e8 };
+pub const WATER_TAIT: LiquidModel = LiquidModel::Tait { exponent: 7.15, bulk_modulus: 3.046e8 };
+
+pub enum Input {
+    UpstreamMachNumber(f64),
+    ShockSpeed(f64),
+    ParticleVelocity(f64),
+    Pressure(f64),
+    PressureRatio(f64),
+    DensityRatio(f64),
+}
+
+pub enum Output {
+    UpstreamMachNumber,
+    DownstreamMachNumber,
+    ShockSpeed,
+    ParticleVelocity,
+    Pressure,
+    PressureRatio,
+    Density,
+    DensityRatio,
+}
+
+#[derive(Debug)]
+pub struct LiquidShock {
+    pub(crate) upstream_mach: f64,     // D / c1
+    pub(crate) downstream_mach: f64,   // (D - u) / c2 relative to the shock
+    pub(crate) shock_speed: f64,       // D
+    pub(crate) particle_velocity: f64, // u behind the shock
+    pub(crate) pressure: f64,          // p2
+    pub(crate) pressure_ratio: f64,    // p2 / p1
+    pub(crate) density: f64,           // ρ2
+    pub(crate) density_ratio: f64,     // ρ2 / ρ1
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LiquidState {
+    pub density: f64,  // kg/m³
+    pub velocity: f64, // m/s
+    pub pressure: f64, // Pa
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum RiemannWave {
+    Shock(f64),            // shock speed
+    Rarefaction(f64, f64), // head and tail speeds
+}
+
+#[derive(Debug)]
+pub struct LiquidRiemannSolution {
+    pub pressure: f64,      // p* either side of the contact
+    pub velocity: f64,      // u* of the contact
+    pub left_density: f64,  // ρ* left of the contact
+    pub right_density: f64, // ρ* right of the contact
+    pub left_wave: RiemannWave,
+    pub right_wave: RiemannWave,
+    left: (LiquidState, LiquidModel),
+    right: (LiquidState, LiquidModel),
+}
+
+impl LiquidModel {
+    fn parameters(&self) -> Result<(f64, f64), CompressibleFlowError> {
+        // (γ, π) of the shifted isentrope
+        let (exponent, offset) = match *self {
+            LiquidModel::StiffenedGas { specific_heat_ratio, stiffness_pressure } => (specific_heat_ratio, stiffness_pressure),
+            LiquidModel::Tait { exponent, bulk_modulus } => (exponent, bulk_modulus),
+        };
+        if !valid_specific_heat_ratio(exponent) {
+            return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+        }
+        if offset < 0.0 {
+            return Err(IsentropicFlowError::InvalidPressureRatio.into());
+        }
+        Ok((exponent, offset))
+    }
+
+    pub fn sound_speed(&self, pressure: f64, density: f64) -> Result<f64, CompressibleFlowError> {
+        let (exponent, offset) = self.parameters()?;
+        if pressure + offset <= 0.0 || density <= 0.0 {
+            return Err(IsentropicFlowError::InvalidPressureRatio.into());
+        }
+        Ok((exponent * (pressure + offset) / density).sqrt())
+    }
+
+    fn shock_density_ratio(&self, shifted_pressure_ratio: f64) -> Result<f64, CompressibleFlowError> {
+        // ρ2 / ρ1 across a shock with (p2 + π) / (p1 + π)
+        let (exponent, _) = self.parameters()?;
+        match self {
+            LiquidModel::StiffenedGas { .. } => Ok(
+                ((exponent + 1.0) * shifted_pressure_ratio + exponent - 1.0) /
+                ((exponent - 1.0) * shifted_pressure_ratio + exponent + 1.0)
+            ),
+            LiquidModel::Tait { .. } => Ok(shifted_pressure_ratio.powf(1.0 / exponent)),
+        }
+    }
+
+    fn shifted_pressure_ratio(&self, density_ratio: f64) -> Result<f64, CompressibleFlowError> {
+        // inverse of shock_density_ratio
+        let (exponent, _) = self.parameters()?;
+        match self {
+            LiquidModel::StiffenedGas { .. } => {
+                if density_ratio >= (exponent + 1.0) / (exponent - 1.0) {
+                    return Err(IsentropicFlowError::InvalidDensityRatio.into());
+                }
+                Ok(((exponent + 1.0) * density_ratio - (exponent - 1.0)) / ((exponent + 1.0) - (exponent - 1.0) * density_ratio))
+            }
+            LiquidModel::Tait { .. } => Ok(density_ratio.powf(exponent)),
+        }
+    }
+}
+
+impl LiquidShock {
+    pub fn new(from: Input, model: LiquidModel, upstream_pressure: f64, upstream_density: f64) -> Result<LiquidShock, CompressibleFlowError> {
+        let (_, offset) = model.parameters()?;
+        let upstream_sound_speed: f64 = model.sound_speed(upstream_pressure, upstream_density)?;
+
+        // speeds grow with the shock strength, so bracket and bisect on p2
+        let from_speed = |target: f64, speed: &dyn Fn(&LiquidShock) -> f64| -> Result<LiquidShock, CompressibleFlowError> {
+            let shock = |shifted_pressure_ratio: f64| {
+                LiquidShock::from_pressure((upstream_pressure + offset) * shifted_pressure_ratio - offset, model, upstream_pressure, upstream_density)
+            };
+            let g = |shifted_pressure_ratio: f64| match shock(shifted_pressure_ratio) {
+                Ok(current) => speed(&current) / target - 1.0,
+                Err(_) => f64::NAN,
+            };
+            let mut upper: f64 = 2.0;
+            while g(upper) < 0.0 {
+                upper *= 2.0;
+                if upper > 1e12 {
+                    return Err(IsentropicFlowError::InvalidMachNumber.into());
+                }
+            }
+            shock(bisection(&g, 1.0 + 1e-12, upper, Some(1e-12), None))
+        };
+
+        match from {
+            Input::UpstreamMachNumber(value) => {
+                if value <= 1.0 {
+                    return Err(IsentropicFlowError::InvalidMachNumber.into());
+                }
+                from_speed(value, &|shock| shock.upstream_mach)
+            }
+            Input::ShockSpeed(value) => {
+                if value <= upstream_sound_speed {
+                    return Err(IsentropicFlowError::InvalidMachNumber.into());
+                }
+                from_speed(value, &|shock| shock.shock_speed)
+            }
+            Input::ParticleVelocity(value) => {
+                if value <= 0.0 {
+                    return Err(IsentropicFlowError::InvalidMachNumber.into());
+                }
+                from_speed(value, &|shock| shock.particle_velocity)
+            }
+            Input::Pressure(value) => {
+                LiquidShock::from_pressure(value, model, upstream_pressure, upstream_density)
+            }
+            Input::PressureRatio(value) => {
+                LiquidShock::from_pressure(value * upstream_pressure, model, upstream_pressure, upstream_density)
+            }
+            Input::DensityRatio(value) => {
+                if value <= 1.0 {
+                    return Err(IsentropicFlowError::InvalidDensityRatio.into());
+                }
+                let shifted_pressure_ratio: f64 = model.shifted_pressure_ratio(value)?;
+                LiquidShock::from_pressure((upstream_pressure + offset) * shifted_pressure_ratio - offset, model, upstream_pressure, upstream_density)
+            }
+        }
+    }
+
+    pub fn from_pressure(pressure: f64, model: LiquidModel, upstream_pressure: f64, upstream_density: f64) -> Result<LiquidShock, CompressibleFlowError> {
+        // mass and momentum give D² = (p2 - p1) / (ρ1 (1 - ρ1 / ρ2)) and
+        // u = D (1 - ρ1 / ρ2), the model only sets ρ2
+        let (_, offset) = model.parameters()?;
+        let upstream_sound_speed: f64 = model.sound_speed(upstream_pressure, upstream_density)?;
+        if pressure <= upstream_pressure {
+            return Err(IsentropicFlowError::InvalidPressureRatio.into());
+        }
+        let density_ratio: f64 = model.shock_density_ratio((pressure + offset) / (upstream_pressure + offset))?;
+        let compression: f64 = 1.0 - 1.0 / density_ratio;
+        if compression <= 0.0 {
+            return Err(IsentropicFlowError::MathError.into());
+        }
+        let shock_speed: f64 = ((pressure - upstream_pressure) / (upstream_density * compression)).sqrt();
+        let particle_velocity: f64 = shock_speed * compression;
+        let density: f64 = upstream_density * density_ratio;
+
+        Ok(LiquidShock {
+            upstream_mach: shock_speed / upstream_sound_speed,
+            downstream_mach: (shock_speed - particle_velocity) / model.sound_speed(pressure, density)?,
+            shock_speed,
+            particle_velocity,
+            pressure,
+            pressure_ratio: pressure / upstream_pressure,
+            density,
+            density_ratio,
+        })
+    }
+}
+
+pub fn calculate(input: Input, output: Output, model: LiquidModel, upstream_pressure: f64, upstream_density: f64) -> Result<f64, CompressibleFlowError> {
+    let liquid_shock = LiquidShock::new(input, model, upstream_pressure, upstream_density)?;
+
+    match output {
+        Output::UpstreamMachNumber => {Ok(liquid_shock.upstream_mach)}
+        Output::DownstreamMachNumber => {Ok(liquid_shock.downstream_mach)}
+        Output::ShockSpeed => {Ok(liquid_shock.shock_speed)}
+        Output::ParticleVelocity => {Ok(liquid_shock.particle_velocity)}
+        Output::Pressure => {Ok(liquid_shock.pressure)}
+        Output::PressureRatio => {Ok(liquid_shock.pressure_ratio)}
+        Output::Density => {Ok(liquid_shock.density)}
+        Output::DensityRatio => {Ok(liquid_shock.density_ratio)}
+    }
+}
+
+pub fn solve_riemann_problem(left: LiquidState, left_model: LiquidModel, right: LiquidState, right_model: LiquidModel) -> Result<LiquidRiemannSolution, CompressibleFlowError> {
+    // exact solver after toro, the velocity change across each wave fK(p) is
+    // √((p - pK) (1 / ρK - 1 / ρ*)) through a shock and the riemann invariant
+    // 2 c / (γ - 1) through a rarefaction, p* solves fL + fR + uR - uL = 0
+    let (left_exponent, left_offset) = left_model.parameters()?;
+    let (right_exponent, right_offset) = right_model.parameters()?;
+    let left_sound_speed: f64 = left_model.sound_speed(left.pressure, left.density)?;
+    let right_sound_speed: f64 = right_model.sound_speed(right.pressure, right.density)?;
+
+    // (velocity change, ρ*) for one side
+    let wave = |pressure: f64, state: LiquidState, model: LiquidModel, exponent: f64, offset: f64, sound_speed: f64| -> Result<(f64, f64), CompressibleFlowError> {
+        let shifted_pressure_ratio: f64 = (pressure + offset) / (state.pressure + offset);
+        if pressure > state.pressure {
+            let density: f64 = state.density * model.shock_density_ratio(shifted_pressure_ratio)?;
+            Ok((((pressure - state.pressure) * (1.0 / state.density - 1.0 / density)).sqrt(), density))
+        } else {
+            let velocity_change: f64 = 2.0 * sound_speed / (exponent - 1.0) * (shifted_pressure_ratio.powf((exponent - 1.0) / (2.0 * exponent)) - 1.0);
+            Ok((velocity_change, state.density * shifted_pressure_ratio.powf(1.0 / exponent)))
+        }
+    };
+    let residual = |pressure: f64| -> Result<f64, CompressibleFlowError> {
+        let (left_change, _) = wave(pressure, left, left_model, left_exponent, left_offset, left_sound_speed)?;
+        let (right_change, _) = wave(pressure, right, right_model, right_exponent, right_offset, right_sound_speed)?;
+        Ok((left_change + right_change + right.velocity - left.velocity) / (left_sound_speed + right_sound_speed))
+    };
+
+    // the lowest pressure either liquid can hold, any lower and it cavitates
+    let lower: f64 = (-left_offset).max(-right_offset);
+    if residual(lower)? > 0.0 {
+        return Err(IsentropicFlowError::InvalidPressureRatio.into());
+    }
+    let scale: f64 = (left.pressure - lower).max(right.pressure - lower);
+    let mut upper: f64 = 2.0;
+    while residual(lower + scale * upper)? < 0.0 {
+        upper *= 2.0;
+    }
+    let g = |x: f64| residual(lower + scale * x).unwrap_or(f64::NAN);
+    let pressure: f64 = lower + scale * bisection(&g, 0.0, upper, Some(1e-12), None);
+
+    let (left_change, left_density) = wave(pressure, left, left_model, left_exponent, left_offset, left_sound_speed)?;
+    let (right_change, right_density) = wave(pressure, right, right_model, right_exponent, right_offset, right_sound_speed)?;
+    let velocity: f64 = 0.5 * (left.velocity + right.velocity) + 0.5 * (right_change - left_change);
+
+    let left_wave: RiemannWave = if pressure > left.pressure {
+        // mass flux through the shock, ρ (u - S) is the same on both sides
+        RiemannWave::Shock(left.velocity - (pressure - left.pressure) / (left.density * left_change))
+    } else {
+        RiemannWave::Rarefaction(left.velocity - left_sound_speed, velocity - left_model.sound_speed(pressure, left_density)?)
+    };
+    let right_wave: RiemannWave = if pressure > right.pressure {
+        RiemannWave::Shock(right.velocity + (pressure - right.pressure) / (right.density * right_change))
+    } else {
+        RiemannWave::Rarefaction(right.velocity + right_sound_speed, velocity + right_model.sound_speed(pressure, right_density)?)
+    };
+
+    Ok(LiquidRiemannSolution {
+        pressure,
+        velocity,
+        left_density,
+        right_density,
+        left_wave,
+        right_wave,
+        left: (left, left_model),
+        right: (right, right_model),
+    })
+}
+
+impl LiquidRiemannSolution {
+    pub fn sample(&self, speed: f64) -> Result<LiquidState, CompressibleFlowError> {
+        // state at x / t = speed with the discontinuity initially at x = 0
+        let (state, model, wave, density, sign) = if speed <= self.velocity {
+            (self.left.0, self.left.1, self.left_wave, self.left_density, 1.0)
+        } else {
+            (self.right.0, self.right.1, self.right_wave, self.right_density, -1.0)
+        };
+        let star: LiquidState = LiquidState { density, velocity: self.velocity, pressure: self.pressure };
+        // distance of the speed past the wave, positive towards the undisturbed side
+        let outside = |wave_speed: f64| sign * (wave_speed - speed) > 0.0;
+
+        match wave {
+            RiemannWave::Shock(shock_speed) => Ok(if outside(shock_speed) { state } else { star }),
+            RiemannWave::Rarefaction(head, tail) => {
+                if outside(head) {
+                    Ok(state)
+                } else if !outside(tail) {
+                    Ok(star)
+                } else {
+                    // inside the fan, u ∓ c = speed and the riemann invariant is carried through
+                    let (exponent, offset) = model.parameters()?;
+                    let sound_speed: f64 = model.sound_speed(state.pressure, state.density)?;
+                    let fan_sound_speed: f64 = 2.0 / (exponent + 1.0) * (sound_speed + sign * (exponent - 1.0) / 2.0 * (state.velocity - speed));
+                    let ratio: f64 = fan_sound_speed / sound_speed;
+                    Ok(LiquidState {
+                        density: state.density * ratio.powf(2.0 / (exponent - 1.0)),
+                        velocity: speed + sign * fan_sound_speed,
+                        pressure: (state.pressure + offset) * ratio.powf(2.0 * exponent / (exponent - 1.0)) - offset,
+                    })
+                }
+            }
+        }
+    }
+}
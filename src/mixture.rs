@@ -0,0 +1,64 @@
+use crate::error::CompressibleFlowError;
+use crate::gas::{Species, UNIVERSAL_GAS_CONSTANT};
+
+
+// ideal mixture of ideal gases, the properties are mole fraction averages of
+// the molar properties, temperatures default to 298.15 K, which only matters
+// when some species are thermally perfect
+const REFERENCE_TEMPERATURE: f64 = 298.15;
+
+#[derive(Debug, Clone)]
+pub struct Mixture {
+    pub species: Vec<(Species, f64)>, // species and mole fraction, fractions sum to 1
+    pub molar_mass: f64,              // kg/mol
+    pub gas_constant: f64,            // J/(kg K)
+}
+
+impl Mixture {
+    pub fn from_mole_fractions(species: &[(Species, f64)]) -> Result<Mixture, CompressibleFlowError> {
+        // fractions are normalised, so parts by volume work as well
+        if species.is_empty() || species.iter().any(|&(current, fraction)| fraction < 0.0 || current.molar_mass <= 0.0) {
+            return Err(CompressibleFlowError::InvalidComposition);
+        }
+        let total: f64 = species.iter().map(|&(_, fraction)| fraction).sum();
+        if total <= 0.0 {
+            return Err(CompressibleFlowError::InvalidComposition);
+        }
+        let species: Vec<(Species, f64)> = species.iter().map(|&(current, fraction)| (current, fraction / total)).collect();
+        let molar_mass: f64 = species.iter().map(|(current, fraction)| fraction * current.molar_mass).sum();
+        Ok(Mixture {
+            species,
+            molar_mass,
+            gas_constant: UNIVERSAL_GAS_CONSTANT / molar_mass,
+        })
+    }
+
+    pub fn from_mass_fractions(species: &[(Species, f64)]) -> Result<Mixture, CompressibleFlowError> {
+        // xi = (yi / Mi) / Σ (yj / Mj)
+        if species.iter().any(|&(current, _)| current.molar_mass <= 0.0) {
+            return Err(CompressibleFlowError::InvalidComposition);
+        }
+        let moles: Vec<(Species, f64)> = species.iter().map(|&(current, fraction)| (current, fraction / current.molar_mass)).collect();
+        Mixture::from_mole_fractions(&moles)
+    }
+
+    pub fn mass_fractions(&self) -> Vec<f64> {
+        self.species.iter().map(|(current, fraction)| fraction * current.molar_mass / self.molar_mass).collect()
+    }
+
+    pub fn specific_heat_cp(&self, temperature: Option<f64>) -> f64 {
+        // J/(kg K)
+        let temperature: f64 = temperature.unwrap_or(REFERENCE_TEMPERATURE);
+        let molar_heat_capacity: f64 = self.species.iter().map(|(current, fraction)| fraction * current.molar_heat_capacity(temperature)).sum();
+        molar_heat_capacity * self.gas_constant
+    }
+
+    pub fn specific_heat_cv(&self, temperature: Option<f64>) -> f64 {
+        self.specific_heat_cp(temperature) - self.gas_constant
+    }
+
+    pub fn specific_heat_ratio(&self, temperature: Option<f64>) -> f64 {
+        // γ for IsentropicFlow, NormalShock and ObliqueShock
+        self.specific_heat_cp(temperature) / self.specific_heat_cv(temperature)
+    }
+}
@@ -0,0 +1,212 @@
+use std::path::Path;
+use crate::isentropic::{self, valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::normalshock::NormalShock;
+
+
+// impact pressures on a triangulated body in hypersonic flow, body axes have x
+// running aft from the nose, z up and y completing the right handed set, so the
+// freestream at angle of attack α moves along (cos α, 0, sin α) and a nose up
+// pitching moment is positive about y
+#[derive(Debug, Clone, Copy)]
+pub enum NewtonianMethod {
+    Newtonian,         // Cp = 2 sin²δ
+    ModifiedNewtonian, // Cp = Cp,max sin²δ, Cp,max behind a normal shock
+}
+
+#[derive(Debug, Clone)]
+pub struct TriangleMesh {
+    // vertices wound counter clockwise seen from outside, as stl requires, the
+    // facet normals stored in the file are ignored since many writers leave them zero
+    pub triangles: Vec<[[f64; 3]; 3]>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReferenceGeometry {
+    pub area: f64,              // S
+    pub length: f64,            // L for the moments
+    pub moment_point: [f64; 3], // moment reference point
+}
+
+#[derive(Debug)]
+pub struct NewtonianAerodynamics {
+    pub angle_of_attack: f64,
+    pub max_pressure_coefficient: f64,    // Cp at the stagnation point
+    pub pressure_coefficients: Vec<f64>,  // Cp on each triangle, 0 in the shadow
+    pub lift_coefficient: f64,
+    pub drag_coefficient: f64,
+    pub side_force_coefficient: f64,
+    pub force_coefficient: [f64; 3],      // F / (q S) in body axes
+    pub moment_coefficient: [f64; 3],     // M / (q S L) in body axes
+    pub pitching_moment_coefficient: f64, // nose up positive
+}
+
+impl TriangleMesh {
+    pub fn from_stl(bytes: &[u8]) -> Result<TriangleMesh, CompressibleFlowError> {
+        // binary when the size matches the triangle count in the header, some
+        // binary files also start with "solid" so that alone decides nothing
+        if bytes.len() >= 84 {
+            let count: usize = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+            if bytes.len() == 84 + 50 * count {
+                return Ok(TriangleMesh { triangles: parse_binary_stl(&bytes[84..], count) });
+            }
+        }
+        let text: &str = std::str::from_utf8(bytes).map_err(|_| CompressibleFlowError::InvalidMesh)?;
+        if !text.trim_start().starts_with("solid") {
+            return Err(CompressibleFlowError::InvalidMesh);
+        }
+        Ok(TriangleMesh { triangles: parse_ascii_stl(text)? })
+    }
+
+    pub fn read_stl<P: AsRef<Path>>(path: P) -> Result<TriangleMesh, CompressibleFlowError> {
+        let bytes: Vec<u8> = std::fs::read(path).map_err(|_| CompressibleFlowError::InvalidMesh)?;
+        TriangleMesh::from_stl(&bytes)
+    }
+
+    pub fn surface_area(&self) -> f64 {
+        self.triangles.iter().map(|triangle| calc_norm(calc_area_vector(triangle))).sum()
+    }
+}
+
+impl NewtonianAerodynamics {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        mesh: &TriangleMesh,
+        freestream_mach: f64,
+        angle_of_attack: f64,
+        method: NewtonianMethod,
+        reference: ReferenceGeometry,
+        specific_heat_ratio: f64,
+    ) -> Result<NewtonianAerodynamics, CompressibleFlowError> {
+        if mesh.triangles.is_empty() {
+            return Err(CompressibleFlowError::InvalidMesh);
+        }
+        if reference.area <= 0.0 || reference.length <= 0.0 {
+            return Err(CompressibleFlowError::InvalidGeometry);
+        }
+        let max_pressure_coefficient: f64 = match method {
+            NewtonianMethod::Newtonian => 2.0,
+            NewtonianMethod::ModifiedNewtonian => calc_max_pressure_coefficient(freestream_mach, specific_heat_ratio)?,
+        };
+        let direction: [f64; 3] = [angle_of_attack.cos(), 0.0, angle_of_attack.sin()];
+
+        let mut pressure_coefficients: Vec<f64> = Vec::with_capacity(mesh.triangles.len());
+        let mut force: [f64; 3] = [0.0; 3];
+        let mut moment: [f64; 3] = [0.0; 3];
+        for triangle in &mesh.triangles {
+            // area vector A n along the outward normal, the face sees the flow
+            // when it points upstream and sin δ = -n · V
+            let area_vector: [f64; 3] = calc_area_vector(triangle);
+            let area: f64 = calc_norm(area_vector);
+            if area == 0.0 {
+                pressure_coefficients.push(0.0);
+                continue;
+            }
+            let sin_incidence: f64 = -(0..3).map(|i| area_vector[i] * direction[i]).sum::<f64>() / area;
+            let pressure_coefficient: f64 = if sin_incidence > 0.0 { max_pressure_coefficient * sin_incidence.powi(2) } else { 0.0 };
+            pressure_coefficients.push(pressure_coefficient);
+
+            // pressure pushes along the inward normal, acting at the centroid
+            let face_force: [f64; 3] = area_vector.map(|component| -pressure_coefficient * component);
+            let arm: [f64; 3] = std::array::from_fn(|i| (triangle[0][i] + triangle[1][i] + triangle[2][i]) / 3.0 - reference.moment_point[i]);
+            let face_moment: [f64; 3] = calc_cross(arm, face_force);
+            for i in 0..3 {
+                force[i] += face_force[i];
+                moment[i] += face_moment[i];
+            }
+        }
+        let force_coefficient: [f64; 3] = force.map(|component| component / reference.area);
+        let moment_coefficient: [f64; 3] = moment.map(|component| component / (reference.area * reference.length));
+
+        Ok(NewtonianAerodynamics {
+            angle_of_attack,
+            max_pressure_coefficient,
+            pressure_coefficients,
+            lift_coefficient: force_coefficient[2] * angle_of_attack.cos() - force_coefficient[0] * angle_of_attack.sin(),
+            drag_coefficient: force_coefficient[0] * angle_of_attack.cos() + force_coefficient[2] * angle_of_attack.sin(),
+            side_force_coefficient: force_coefficient[1],
+            force_coefficient,
+            moment_coefficient,
+            pitching_moment_coefficient: moment_coefficient[1],
+        })
+    }
+
+    pub fn sweep(
+        mesh: &TriangleMesh,
+        freestream_mach: f64,
+        angles_of_attack: &[f64],
+        method: NewtonianMethod,
+        reference: ReferenceGeometry,
+        specific_heat_ratio: f64,
+    ) -> Result<Vec<NewtonianAerodynamics>, CompressibleFlowError> {
+        angles_of_attack.iter()
+            .map(|&angle_of_attack| NewtonianAerodynamics::new(mesh, freestream_mach, angle_of_attack, method, reference, specific_heat_ratio))
+            .collect()
+    }
+}
+
+pub fn calc_max_pressure_coefficient(freestream_mach: f64, specific_heat_ratio: f64) -> Result<f64, CompressibleFlowError> {
+    // Cp,max = (p02 / p∞ - 1) / (γ M² / 2) with p02 the pitot pressure behind the normal shock
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    if freestream_mach <= 1.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    let normal_shock: NormalShock = NormalShock::from_upstream_mach(freestream_mach, specific_heat_ratio)?;
+    let pitot_pressure_ratio: f64 = normal_shock.stagnation_pressure_ratio / isentropic::calc_pressure_ratio_from_mach(freestream_mach, specific_heat_ratio)?;
+    Ok(2.0 / (specific_heat_ratio * freestream_mach.powi(2)) * (pitot_pressure_ratio - 1.0))
+}
+
+fn parse_binary_stl(records: &[u8], count: usize) -> Vec<[[f64; 3]; 3]> {
+    // 50 byte records, normal then three vertices as little endian f32 and a
+    // two byte attribute count
+    let read = |offset: usize| f32::from_le_bytes([records[offset], records[offset + 1], records[offset + 2], records[offset + 3]]) as f64;
+    (0..count)
+        .map(|n| std::array::from_fn(|vertex| std::array::from_fn(|i| read(50 * n + 12 * (vertex + 1) + 4 * i))))
+        .collect()
+}
+
+fn parse_ascii_stl(text: &str) -> Result<Vec<[[f64; 3]; 3]>, CompressibleFlowError> {
+    // every facet holds an outer loop of exactly three vertex lines
+    let mut triangles: Vec<[[f64; 3]; 3]> = Vec::new();
+    let mut vertices: Vec<[f64; 3]> = Vec::with_capacity(3);
+    for line in text.lines() {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("vertex") => {
+                let coordinates: Vec<f64> = words.map(|word| word.parse::<f64>()).collect::<Result<_, _>>().map_err(|_| CompressibleFlowError::InvalidMesh)?;
+                if coordinates.len() != 3 || vertices.len() == 3 {
+                    return Err(CompressibleFlowError::InvalidMesh);
+                }
+                vertices.push([coordinates[0], coordinates[1], coordinates[2]]);
+            }
+            Some("endfacet") => {
+                if vertices.len() != 3 {
+                    return Err(CompressibleFlowError::InvalidMesh);
+                }
+                triangles.push([vertices[0], vertices[1], vertices[2]]);
+                vertices.clear();
+            }
+            _ => {}
+        }
+    }
+    if triangles.is_empty() || !vertices.is_empty() {
+        return Err(CompressibleFlowError::InvalidMesh);
+    }
+    Ok(triangles)
+}
+
+fn calc_area_vector(triangle: &[[f64; 3]; 3]) -> [f64; 3] {
+    // (b - a) × (c - a) / 2, outward for counter clockwise vertices
+    let edge: [[f64; 3]; 2] = [1, 2].map(|vertex| std::array::from_fn(|i| triangle[vertex][i] - triangle[0][i]));
+    calc_cross(edge[0], edge[1]).map(|component| component / 2.0)
+}
+
+fn calc_cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn calc_norm(a: [f64; 3]) -> f64 {
+    (a[0].powi(2) + a[1].powi(2) + a[2].powi(2)).sqrt()
+}
@@ -1,5 +1,6 @@
 use crate::isentropic::{valid_specific_heat_ratio, IsentropicFlowError}; 
 use crate::numerics::{bisection, newton_raphson};
+use crate::gas::GasProperties;
 
 
 pub enum Input {
@@ -9,6 +10,10 @@ pub enum Input {
     PressureRatio(f64),
     DensityRatio(f64),
     StagnationPressureRatio(f64),
+    EntropyChange(f64),
+    VelocityRatio(f64),
+    InternalEnergyChange(f64),
+    EnthalpyChange(f64),
 }
 
 pub enum Output {
@@ -18,16 +23,26 @@ pub enum Output {
     PressureRatio,
     DensityRatio,
     StagnationPressureRatio,
+    EntropyChange,
+    StagnationTemperatureRatio,
+    VelocityRatio,
+    InternalEnergyChange,
+    EnthalpyChange,
 }
 
 #[derive(Debug)]
 pub struct NormalShock {
-    upstream_mach: f64,             // M1
-    downstream_mach: f64,           // M2
-    temperature_ratio: f64,         // T2 / T1 (static temperature ratio)
-    pressure_ratio: f64,            // p2 / p1
-    density_ratio: f64,             // ρ2 / ρ1
-    stagnation_pressure_ratio: f64, // p02 / p01
+    pub(crate) upstream_mach: f64,                // M1
+    pub(crate) downstream_mach: f64,              // M2
+    pub(crate) temperature_ratio: f64,            // T2 / T1 (static temperature ratio)
+    pub(crate) pressure_ratio: f64,               // p2 / p1
+    pub(crate) density_ratio: f64,                // ρ2 / ρ1
+    pub(crate) stagnation_pressure_ratio: f64,    // p02 / p01
+    pub(crate) entropy_change: f64,               // (s2 - s1) / R
+    pub(crate) stagnation_temperature_ratio: f64, // T02 / T01
+    pub(crate) velocity_ratio: f64,               // u2 / u1
+    pub(crate) internal_energy_change: f64,       // (e2 - e1) / (p1 v1), hugoniot energy term
+    pub(crate) enthalpy_change: f64,              // (h2 - h1) / (p1 v1)
 }
 
 impl NormalShock {
@@ -36,7 +51,7 @@ impl NormalShock {
             return Err(IsentropicFlowError::InvalidSpecificHeatRatio);
         }
 
-        let shock = match from {
+        match from {
             Input::UpstreamMachNumber(value) => {
                 NormalShock::from_upstream_mach(value, specific_heat_ratio)
             }
@@ -60,8 +75,23 @@ impl NormalShock {
                 let upstream_mach = calc_upstream_mach_from_stagnation_pressure_ratio(value, specific_heat_ratio)?;
                 NormalShock::from_upstream_mach(upstream_mach, specific_heat_ratio)
             }
-        };
-        Ok(shock?)
+            Input::EntropyChange(value) => {
+                let upstream_mach = calc_upstream_mach_from_entropy_change(value, specific_heat_ratio)?;
+                NormalShock::from_upstream_mach(upstream_mach, specific_heat_ratio)
+            }
+            Input::VelocityRatio(value) => {
+                let upstream_mach = calc_upstream_mach_from_velocity_ratio(value, specific_heat_ratio)?;
+                NormalShock::from_upstream_mach(upstream_mach, specific_heat_ratio)
+            }
+            Input::InternalEnergyChange(value) => {
+                let upstream_mach = calc_upstream_mach_from_internal_energy_change(value, specific_heat_ratio)?;
+                NormalShock::from_upstream_mach(upstream_mach, specific_heat_ratio)
+            }
+            Input::EnthalpyChange(value) => {
+                let upstream_mach = calc_upstream_mach_from_enthalpy_change(value, specific_heat_ratio)?;
+                NormalShock::from_upstream_mach(upstream_mach, specific_heat_ratio)
+            }
+        }
     }
 
     pub fn from_upstream_mach(upstream_mach: f64, specific_heat_ratio: f64) -> Result<NormalShock, IsentropicFlowError> {
@@ -75,6 +105,11 @@ impl NormalShock {
         let temperature_ratio = calc_temperature_ratio_from_upstream_mach(upstream_mach, specific_heat_ratio)?;
         let density_ratio = calc_density_ratio_from_upstream_mach(upstream_mach, specific_heat_ratio)?;
         let stagnation_pressure_ratio = calc_stagnation_pressure_ratio_from_upstream_mach(upstream_mach, specific_heat_ratio)?;
+        let entropy_change = calc_entropy_change_from_upstream_mach(upstream_mach, specific_heat_ratio)?;
+        let stagnation_temperature_ratio = calc_stagnation_temperature_ratio_from_upstream_mach(upstream_mach, specific_heat_ratio)?;
+        let velocity_ratio = calc_velocity_ratio_from_upstream_mach(upstream_mach, specific_heat_ratio)?;
+        let internal_energy_change = calc_internal_energy_change_from_upstream_mach(upstream_mach, specific_heat_ratio)?;
+        let enthalpy_change = calc_enthalpy_change_from_upstream_mach(upstream_mach, specific_heat_ratio)?;
         
         Ok(NormalShock{
             upstream_mach,
@@ -83,6 +118,11 @@ impl NormalShock {
             pressure_ratio,
             density_ratio,
             stagnation_pressure_ratio,
+            entropy_change,
+            stagnation_temperature_ratio,
+            velocity_ratio,
+            internal_energy_change,
+            enthalpy_change,
         })
     }
 }
@@ -101,9 +141,19 @@ pub fn calculate(input: Input, output: Output, specific_heat_ratio: f64) -> Resu
         Output::PressureRatio => {Ok(normal_shock.pressure_ratio)}
         Output::DensityRatio => {Ok(normal_shock.density_ratio)}
         Output::StagnationPressureRatio => {Ok(normal_shock.stagnation_pressure_ratio)}
+        Output::EntropyChange => {Ok(normal_shock.entropy_change)}
+        Output::StagnationTemperatureRatio => {Ok(normal_shock.stagnation_temperature_ratio)}
+        Output::VelocityRatio => {Ok(normal_shock.velocity_ratio)}
+        Output::InternalEnergyChange => {Ok(normal_shock.internal_energy_change)}
+        Output::EnthalpyChange => {Ok(normal_shock.enthalpy_change)}
     }
 }
 
+pub fn calculate_for_gas(input: Input, output: Output, gas: GasProperties) -> Result<f64, IsentropicFlowError> {
+    // same as calculate with γ taken from a catalogue gas
+    calculate(input, output, gas.specific_heat_ratio)
+}
+
 pub fn calc_downstream_mach_from_upstream_mach(upstream_mach: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
     if !valid_specific_heat_ratio(specific_heat_ratio) {
         return Err(IsentropicFlowError::InvalidSpecificHeatRatio);
@@ -155,6 +205,36 @@ pub fn calc_stagnation_pressure_ratio_from_upstream_mach(upstream_mach: f64, spe
     Ok(stagnation_pressure_ratio)
 }
 
+pub fn calc_entropy_change_from_upstream_mach(upstream_mach: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
+    // Δs / R = -ln(p02 / p01), the stagnation temperature is unchanged
+    Ok(-calc_stagnation_pressure_ratio_from_upstream_mach(upstream_mach, specific_heat_ratio)?.ln())
+}
+
+pub fn calc_stagnation_temperature_ratio_from_upstream_mach(upstream_mach: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
+    // T02 / T01 = (T2 / T1) (T02 / T2) / (T01 / T1), unity for an adiabatic shock
+    let downstream_mach: f64 = calc_downstream_mach_from_upstream_mach(upstream_mach, specific_heat_ratio)?;
+    let stagnation_temperature_ratio: f64 = 
+        calc_temperature_ratio_from_upstream_mach(upstream_mach, specific_heat_ratio)? *
+        (1.0 + (specific_heat_ratio - 1.0) / 2.0 * downstream_mach.powi(2)) /
+        (1.0 + (specific_heat_ratio - 1.0) / 2.0 * upstream_mach.powi(2));
+    Ok(stagnation_temperature_ratio)
+}
+
+pub fn calc_velocity_ratio_from_upstream_mach(upstream_mach: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
+    // u2 / u1 = ρ1 / ρ2 from continuity
+    Ok(1.0 / calc_density_ratio_from_upstream_mach(upstream_mach, specific_heat_ratio)?)
+}
+
+pub fn calc_internal_energy_change_from_upstream_mach(upstream_mach: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
+    // (e2 - e1) / (p1 v1) = (T2 / T1 - 1) / (γ - 1), equal to (p2 / p1 + 1) (1 - v2 / v1) / 2 on the hugoniot
+    Ok((calc_temperature_ratio_from_upstream_mach(upstream_mach, specific_heat_ratio)? - 1.0) / (specific_heat_ratio - 1.0))
+}
+
+pub fn calc_enthalpy_change_from_upstream_mach(upstream_mach: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
+    // (h2 - h1) / (p1 v1) = γ (T2 / T1 - 1) / (γ - 1)
+    Ok(specific_heat_ratio * calc_internal_energy_change_from_upstream_mach(upstream_mach, specific_heat_ratio)?)
+}
+
 pub fn calc_upstream_mach_from_downstream_mach(downstream_mach: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
     if !valid_specific_heat_ratio(specific_heat_ratio) {
         return Err(IsentropicFlowError::InvalidSpecificHeatRatio);
@@ -191,7 +271,7 @@ pub fn calc_upstream_mach_from_temperature_ratio(temperature_ratio: f64, specifi
     if discriminant < 0.0 {
         return Err(IsentropicFlowError::WhatTheFuck);
     }
-    let upstream_mach: f64 = (-b + discriminant.sqrt()) / (2.0 * a).sqrt();
+    let upstream_mach: f64 = ((-b + discriminant.sqrt()) / (2.0 * a)).sqrt();
     Ok(upstream_mach)
 }
 
@@ -246,4 +326,39 @@ pub fn calc_upstream_mach_from_stagnation_pressure_ratio(stagnation_pressure_rat
 
     let upstream_mach = newton_raphson(&f, &df, 2.0, None, None);
     Ok(upstream_mach)
-}
\ No newline at end of file
+}
+
+pub fn calc_upstream_mach_from_entropy_change(entropy_change: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
+    if entropy_change <= 0.0 {
+        return Err(IsentropicFlowError::InvalidPressureRatio);
+    }
+    calc_upstream_mach_from_stagnation_pressure_ratio((-entropy_change).exp(), specific_heat_ratio)
+}
+
+pub fn calc_upstream_mach_from_velocity_ratio(velocity_ratio: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
+    // u2 / u1 falls from 1 towards (γ - 1) / (γ + 1) as the shock strengthens
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio);
+    }
+    if velocity_ratio >= 1.0 || velocity_ratio <= (specific_heat_ratio - 1.0) / (specific_heat_ratio + 1.0) {
+        return Err(IsentropicFlowError::InvalidDensityRatio);
+    }
+    calc_upstream_mach_from_density_ratio(1.0 / velocity_ratio, specific_heat_ratio)
+}
+
+pub fn calc_upstream_mach_from_internal_energy_change(internal_energy_change: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio);
+    }
+    if internal_energy_change <= 0.0 {
+        return Err(IsentropicFlowError::InvalidTemperatureRatio);
+    }
+    calc_upstream_mach_from_temperature_ratio(1.0 + (specific_heat_ratio - 1.0) * internal_energy_change, specific_heat_ratio)
+}
+
+pub fn calc_upstream_mach_from_enthalpy_change(enthalpy_change: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio);
+    }
+    calc_upstream_mach_from_internal_energy_change(enthalpy_change / specific_heat_ratio, specific_heat_ratio)
+}
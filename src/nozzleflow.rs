@@ -0,0 +1,336 @@
+use crate::isentropic::{self, valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::normalshock;
+
+
+pub enum OutletCondition {
+    Supersonic,              // all exit quantities extrapolated from the interior
+    BackPressureRatio(f64),  // pe / p0, fixes the exit static pressure (subsonic outflow)
+}
+
+#[derive(Debug)]
+pub struct NozzleFlow {
+    // all quantities are nondimensionalised by the reservoir conditions
+    // and the throat area, velocity is nondimensionalised by a0
+    pub x: Vec<f64>,                    // axial position
+    pub area_ratio: Vec<f64>,           // A / A*
+    pub mach_number: Vec<f64>,          // M
+    pub velocity: Vec<f64>,             // V / a0
+    pub density_ratio: Vec<f64>,        // ρ / ρ0
+    pub temperature_ratio: Vec<f64>,    // T / T0
+    pub pressure_ratio: Vec<f64>,       // p / p0
+    pub mass_flow: Vec<f64>,            // ρVA / (ρ0 a0 A*)
+    pub residual_history: Vec<f64>,     // max |d(ρ/ρ0)/dt| at each time step
+    pub iterations: u32,
+    pub converged: bool,
+}
+
+impl NozzleFlow {
+    pub fn isentropic_mach_number(&self, specific_heat_ratio: f64) -> Result<Vec<f64>, CompressibleFlowError> {
+        // exact shock free solution on the same grid, subsonic upstream of the
+        // throat and supersonic downstream of it
+        let throat_index = throat_index(&self.area_ratio);
+        self.area_ratio.iter()
+            .enumerate()
+            .map(|(i, &area_ratio)| {
+                Ok(isentropic::calc_mach_from_area_ratio(area_ratio.max(1.0), specific_heat_ratio, i > throat_index)?)
+            })
+            .collect()
+    }
+
+    pub fn shock_position(&self) -> Option<f64> {
+        // the captured shock sits where the pressure rises most steeply,
+        // returns none if the pressure never rises in the flow direction
+        let mut shock_position: Option<f64> = None;
+        let mut max_gradient: f64 = 0.0;
+        for i in 0..self.x.len() - 1 {
+            let gradient = (self.pressure_ratio[i + 1] - self.pressure_ratio[i]) / (self.x[i + 1] - self.x[i]);
+            if gradient > max_gradient {
+                max_gradient = gradient;
+                shock_position = Some(0.5 * (self.x[i] + self.x[i + 1]));
+            }
+        }
+        shock_position
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn solve_nozzle_flow(
+    area: &impl Fn(f64) -> f64, // A(x), any consistent units
+    x_start: f64,
+    x_end: f64,
+    grid_points: usize,
+    outlet: OutletCondition,
+    specific_heat_ratio: f64,
+    courant_number: Option<f64>,
+    artificial_viscosity: Option<f64>,
+    tolerance: Option<f64>,
+    max_iters: Option<u32>,
+) -> Result<NozzleFlow, CompressibleFlowError> {
+    // marches the quasi one dimensional euler equations in conservation form to
+    // steady state using maccormack's predictor-corrector scheme with
+    // pressure switched artificial viscosity (anderson, cfd, ch. 7)
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    if grid_points < 5 || x_end <= x_start {
+        return Err(CompressibleFlowError::InvalidGridPoints);
+    }
+    if let OutletCondition::BackPressureRatio(value) = outlet {
+        if value <= 0.0 || value >= 1.0 {
+            return Err(IsentropicFlowError::InvalidPressureRatio.into());
+        }
+    }
+
+    let courant_number: f64 = courant_number.unwrap_or(0.5);
+    let artificial_viscosity: f64 = artificial_viscosity.unwrap_or(0.2);
+    let tolerance: f64 = tolerance.unwrap_or(1e-6);
+    let max_iters: u32 = max_iters.unwrap_or(50000);
+    let gamma: f64 = specific_heat_ratio;
+    let n: usize = grid_points;
+
+    // set up the grid and nondimensionalise the area by the throat area
+    let dx: f64 = (x_end - x_start) / (n - 1) as f64;
+    let x: Vec<f64> = (0..n).map(|i| x_start + i as f64 * dx).collect();
+    let dimensional_area: Vec<f64> = x.iter().map(|&x| area(x)).collect();
+    if dimensional_area.iter().any(|&a| a <= 0.0 || !a.is_finite()) {
+        return Err(IsentropicFlowError::InvalidAreaRatio.into());
+    }
+    let throat_area: f64 = dimensional_area.iter().cloned().fold(f64::INFINITY, f64::min);
+    let area_ratio: Vec<f64> = dimensional_area.iter().map(|&a| a / throat_area).collect();
+    let throat: usize = throat_index(&area_ratio);
+
+    // central difference area gradient, one sided at the ends
+    let area_gradient: Vec<f64> = (0..n).map(|i| {
+        if i == 0 {
+            (area_ratio[1] - area_ratio[0]) / dx
+        } else if i == n - 1 {
+            (area_ratio[n - 1] - area_ratio[n - 2]) / dx
+        } else {
+            (area_ratio[i + 1] - area_ratio[i - 1]) / (2.0 * dx)
+        }
+    }).collect();
+
+    // initialise from the shock free isentropic solution
+    let mut u1: Vec<f64> = vec![0.0; n]; // ρA
+    let mut u2: Vec<f64> = vec![0.0; n]; // ρAV
+    let mut u3: Vec<f64> = vec![0.0; n]; // ρA(e / (γ - 1) + γV² / 2)
+    for i in 0..n {
+        let mach_number = isentropic::calc_mach_from_area_ratio(area_ratio[i], gamma, i > throat)?;
+        let temperature = isentropic::calc_temperature_ratio_from_mach(mach_number, gamma)?;
+        let density = isentropic::calc_density_ratio_from_mach(mach_number, gamma)?;
+        let velocity = mach_number * temperature.sqrt();
+        u1[i] = density * area_ratio[i];
+        u2[i] = density * area_ratio[i] * velocity;
+        u3[i] = density * area_ratio[i] * (temperature / (gamma - 1.0) + gamma / 2.0 * velocity.powi(2));
+    }
+
+    let mut residual_history: Vec<f64> = Vec::new();
+    let mut converged: bool = false;
+    let mut iterations: u32 = 0;
+
+    while iterations < max_iters {
+        iterations += 1;
+
+        // time step from the local cfl condition
+        let dt: f64 = (0..n).map(|i| {
+            let (_, velocity, temperature) = primitives(u1[i], u2[i], u3[i], area_ratio[i], gamma);
+            courant_number * dx / (temperature.sqrt() + velocity.abs())
+        }).fold(f64::INFINITY, f64::min);
+        if !dt.is_finite() {
+            return Err(IsentropicFlowError::MathError.into());
+        }
+
+        // predictor step, forward differences
+        let (f1, f2, f3) = fluxes(&u1, &u2, &u3, gamma);
+        let pressure: Vec<f64> = pressures(&u1, &u2, &u3, &area_ratio, gamma);
+        let mut du1_predicted: Vec<f64> = vec![0.0; n];
+        let mut du2_predicted: Vec<f64> = vec![0.0; n];
+        let mut du3_predicted: Vec<f64> = vec![0.0; n];
+        let mut u1_predicted: Vec<f64> = u1.clone();
+        let mut u2_predicted: Vec<f64> = u2.clone();
+        let mut u3_predicted: Vec<f64> = u3.clone();
+        for i in 1..n - 1 {
+            let source: f64 = pressure[i] * area_gradient[i] / gamma;
+            du1_predicted[i] = -(f1[i + 1] - f1[i]) / dx;
+            du2_predicted[i] = -(f2[i + 1] - f2[i]) / dx + source;
+            du3_predicted[i] = -(f3[i + 1] - f3[i]) / dx;
+
+            let switch: f64 = viscosity_switch(&pressure, i, artificial_viscosity);
+            u1_predicted[i] = u1[i] + du1_predicted[i] * dt + switch * (u1[i + 1] - 2.0 * u1[i] + u1[i - 1]);
+            u2_predicted[i] = u2[i] + du2_predicted[i] * dt + switch * (u2[i + 1] - 2.0 * u2[i] + u2[i - 1]);
+            u3_predicted[i] = u3[i] + du3_predicted[i] * dt + switch * (u3[i + 1] - 2.0 * u3[i] + u3[i - 1]);
+        }
+
+        // corrector step, rearward differences on the predicted values
+        let (f1, f2, f3) = fluxes(&u1_predicted, &u2_predicted, &u3_predicted, gamma);
+        let pressure_predicted: Vec<f64> = pressures(&u1_predicted, &u2_predicted, &u3_predicted, &area_ratio, gamma);
+        let mut residual: f64 = 0.0;
+        let previous_density: Vec<f64> = (0..n).map(|i| u1[i] / area_ratio[i]).collect();
+        let (mut u1_next, mut u2_next, mut u3_next) = (u1.clone(), u2.clone(), u3.clone());
+        for i in 1..n - 1 {
+            let source: f64 = pressure_predicted[i] * area_gradient[i] / gamma;
+            let du1_corrected: f64 = -(f1[i] - f1[i - 1]) / dx;
+            let du2_corrected: f64 = -(f2[i] - f2[i - 1]) / dx + source;
+            let du3_corrected: f64 = -(f3[i] - f3[i - 1]) / dx;
+
+            let du1_average: f64 = 0.5 * (du1_predicted[i] + du1_corrected);
+            let du2_average: f64 = 0.5 * (du2_predicted[i] + du2_corrected);
+            let du3_average: f64 = 0.5 * (du3_predicted[i] + du3_corrected);
+
+            let switch: f64 = viscosity_switch(&pressure_predicted, i, artificial_viscosity);
+            u1_next[i] = u1[i] + du1_average * dt
+                + switch * (u1_predicted[i + 1] - 2.0 * u1_predicted[i] + u1_predicted[i - 1]);
+            u2_next[i] = u2[i] + du2_average * dt
+                + switch * (u2_predicted[i + 1] - 2.0 * u2_predicted[i] + u2_predicted[i - 1]);
+            u3_next[i] = u3[i] + du3_average * dt
+                + switch * (u3_predicted[i + 1] - 2.0 * u3_predicted[i] + u3_predicted[i - 1]);
+        }
+        u1 = u1_next;
+        u2 = u2_next;
+        u3 = u3_next;
+
+        // inflow boundary, subsonic from the reservoir so ρ and T are fixed
+        // and the mass flux floats
+        u1[0] = area_ratio[0];
+        u2[0] = 2.0 * u2[1] - u2[2];
+        let inlet_velocity: f64 = u2[0] / u1[0];
+        u3[0] = u1[0] * (1.0 / (gamma - 1.0) + gamma / 2.0 * inlet_velocity.powi(2));
+
+        // outflow boundary, extrapolate everything then overwrite the energy
+        // if the exit pressure is imposed
+        u1[n - 1] = 2.0 * u1[n - 2] - u1[n - 3];
+        u2[n - 1] = 2.0 * u2[n - 2] - u2[n - 3];
+        u3[n - 1] = 2.0 * u3[n - 2] - u3[n - 3];
+        if let OutletCondition::BackPressureRatio(exit_pressure) = outlet {
+            let exit_velocity: f64 = u2[n - 1] / u1[n - 1];
+            u3[n - 1] = exit_pressure * area_ratio[n - 1] / (gamma - 1.0)
+                + gamma / 2.0 * u2[n - 1] * exit_velocity;
+        }
+
+        // convergence based on the rate of change of density
+        for i in 0..n {
+            let density: f64 = u1[i] / area_ratio[i];
+            if !density.is_finite() || density <= 0.0 {
+                return Err(IsentropicFlowError::MathError.into());
+            }
+            residual = residual.max(((density - previous_density[i]) / dt).abs());
+        }
+        residual_history.push(residual);
+
+        if residual < tolerance {
+            converged = true;
+            break;
+        }
+    }
+
+    // decode the primitive variables
+    let mut mach_number: Vec<f64> = Vec::with_capacity(n);
+    let mut velocity: Vec<f64> = Vec::with_capacity(n);
+    let mut density_ratio: Vec<f64> = Vec::with_capacity(n);
+    let mut temperature_ratio: Vec<f64> = Vec::with_capacity(n);
+    let mut pressure_ratio: Vec<f64> = Vec::with_capacity(n);
+    let mut mass_flow: Vec<f64> = Vec::with_capacity(n);
+    for i in 0..n {
+        let (density, v, temperature) = primitives(u1[i], u2[i], u3[i], area_ratio[i], gamma);
+        mach_number.push(v / temperature.sqrt());
+        velocity.push(v);
+        density_ratio.push(density);
+        temperature_ratio.push(temperature);
+        pressure_ratio.push(density * temperature);
+        mass_flow.push(u2[i]);
+    }
+
+    Ok(NozzleFlow {
+        x,
+        area_ratio,
+        mach_number,
+        velocity,
+        density_ratio,
+        temperature_ratio,
+        pressure_ratio,
+        mass_flow,
+        residual_history,
+        iterations,
+        converged,
+    })
+}
+
+pub fn calc_shock_area_ratio(exit_area_ratio: f64, back_pressure_ratio: f64, specific_heat_ratio: f64) -> Result<(f64, f64), CompressibleFlowError> {
+    // exact location of a normal shock standing in the diverging section for
+    // a given pe / p0 and Ae / A*, returns (As / A*, M1 at the shock)
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    if exit_area_ratio < 1.0 {
+        return Err(IsentropicFlowError::InvalidAreaRatio.into());
+    }
+    if back_pressure_ratio <= 0.0 || back_pressure_ratio >= 1.0 {
+        return Err(IsentropicFlowError::InvalidPressureRatio.into());
+    }
+    let gamma: f64 = specific_heat_ratio;
+
+    // (pe / p01)(Ae / A1*) fixes the subsonic exit mach number
+    let exit_product: f64 = back_pressure_ratio * exit_area_ratio;
+    let exit_mach_squared: f64 =
+        (-1.0 / (gamma - 1.0)) +
+        (1.0 / (gamma - 1.0).powi(2)
+            + (2.0 / (gamma - 1.0)) * (2.0 / (gamma + 1.0)).powf((gamma + 1.0) / (gamma - 1.0)) / exit_product.powi(2)
+        ).sqrt();
+    let exit_mach: f64 = exit_mach_squared.sqrt();
+
+    // stagnation pressure loss across the shock
+    let exit_stagnation_pressure_ratio: f64 = back_pressure_ratio / isentropic::calc_pressure_ratio_from_mach(exit_mach, gamma)?;
+    if exit_stagnation_pressure_ratio >= 1.0 {
+        // back pressure too high for a shock to sit in the nozzle
+        return Err(IsentropicFlowError::InvalidPressureRatio.into());
+    }
+    let shock_mach: f64 = normalshock::calc_upstream_mach_from_stagnation_pressure_ratio(exit_stagnation_pressure_ratio, gamma)?;
+    let shock_area_ratio: f64 = isentropic::calc_area_ratio_from_mach(shock_mach, gamma)?;
+    if shock_area_ratio > exit_area_ratio {
+        // shock would have to sit downstream of the exit
+        return Err(IsentropicFlowError::InvalidPressureRatio.into());
+    }
+
+    Ok((shock_area_ratio, shock_mach))
+}
+
+fn throat_index(area_ratio: &[f64]) -> usize {
+    area_ratio.iter()
+        .enumerate()
+        .fold((0, f64::INFINITY), |(index, min), (i, &a)| if a < min { (i, a) } else { (index, min) })
+        .0
+}
+
+fn primitives(u1: f64, u2: f64, u3: f64, area_ratio: f64, specific_heat_ratio: f64) -> (f64, f64, f64) {
+    // returns (ρ, V, T) from the conservative variables
+    let density: f64 = u1 / area_ratio;
+    let velocity: f64 = u2 / u1;
+    let temperature: f64 = (specific_heat_ratio - 1.0) * (u3 / u1 - specific_heat_ratio / 2.0 * velocity.powi(2));
+    (density, velocity, temperature)
+}
+
+fn pressures(u1: &[f64], u2: &[f64], u3: &[f64], area_ratio: &[f64], specific_heat_ratio: f64) -> Vec<f64> {
+    (0..u1.len()).map(|i| {
+        let (density, _, temperature) = primitives(u1[i], u2[i], u3[i], area_ratio[i], specific_heat_ratio);
+        density * temperature
+    }).collect()
+}
+
+fn fluxes(u1: &[f64], u2: &[f64], u3: &[f64], specific_heat_ratio: f64) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let gamma: f64 = specific_heat_ratio;
+    let f1: Vec<f64> = u2.to_vec();
+    let f2: Vec<f64> = (0..u1.len()).map(|i| {
+        u2[i].powi(2) / u1[i] + ((gamma - 1.0) / gamma) * (u3[i] - gamma / 2.0 * u2[i].powi(2) / u1[i])
+    }).collect();
+    let f3: Vec<f64> = (0..u1.len()).map(|i| {
+        gamma * u2[i] * u3[i] / u1[i] - gamma * (gamma - 1.0) / 2.0 * u2[i].powi(3) / u1[i].powi(2)
+    }).collect();
+    (f1, f2, f3)
+}
+
+fn viscosity_switch(pressure: &[f64], i: usize, coefficient: f64) -> f64 {
+    // second difference pressure sensor, only switches on near the shock
+    coefficient * (pressure[i + 1] - 2.0 * pressure[i] + pressure[i - 1]).abs()
+        / (pressure[i + 1] + 2.0 * pressure[i] + pressure[i - 1])
+}
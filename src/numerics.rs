@@ -1,3 +1,6 @@
+use crate::isentropic::IsentropicFlowError;
+
+
 pub fn bisection (
     f: &impl Fn(f64) -> f64,
     x1: f64, // 1st solution bound
@@ -12,18 +15,22 @@ pub fn bisection (
     // initialise lower and upper bound according to given bounds
     let (mut lowerbound, mut upperbound) = if x1 < x2 { (x1, x2) } else { (x2, x1) };
 
-    // iterate
+    // iterate, keeping the function value at the lower bound so each
+    // iteration costs a single evaluation
+    let mut f_lowerbound = f(lowerbound);
     for _ in 0..max_iters {
         let midpoint = (upperbound + lowerbound) / 2.0;
+        let f_midpoint = f(midpoint);
         
         // check convergence
-        if f(midpoint).abs() < tolerance || (upperbound - lowerbound) / 2.0 < tolerance {
+        if f_midpoint.abs() < tolerance || (upperbound - lowerbound) / 2.0 < tolerance {
             return midpoint;
         }
 
         // update bounds
-        if (f(midpoint) * f(lowerbound)) > 0.0 {
+        if (f_midpoint * f_lowerbound) > 0.0 {
             lowerbound = midpoint;
+            f_lowerbound = f_midpoint;
         } else {
             upperbound = midpoint;
         }
@@ -77,4 +84,123 @@ pub fn newton_raphson(
     }
 
     panic!("solution not converged");
-}
\ No newline at end of file
+}
+pub fn golden_section_maximum(
+    f: &impl Fn(f64) -> f64,
+    x1: f64, // 1st search bound
+    x2: f64, // 2nd search bound
+    tolerance: Option<f64>,
+    max_iters: Option<u16>,
+) -> f64 {
+    // location of the maximum of a unimodal function, nan counts as lower than
+    // any number so the search keeps away from where f fails
+    let tolerance = tolerance.unwrap_or(1e-9);
+    let max_iters = max_iters.unwrap_or(200);
+    let ratio: f64 = (5.0_f64.sqrt() - 1.0) / 2.0;
+    let value = |x: f64| {
+        let y = f(x);
+        if y.is_nan() { f64::NEG_INFINITY } else { y }
+    };
+
+    let (mut lowerbound, mut upperbound) = if x1 < x2 { (x1, x2) } else { (x2, x1) };
+    let mut left: f64 = upperbound - ratio * (upperbound - lowerbound);
+    let mut right: f64 = lowerbound + ratio * (upperbound - lowerbound);
+    let (mut f_left, mut f_right) = (value(left), value(right));
+    for _ in 0..max_iters {
+        if (upperbound - lowerbound) / 2.0 < tolerance {
+            break;
+        }
+        // keep the side holding the larger interior value
+        if f_left >= f_right {
+            upperbound = right;
+            right = left;
+            f_right = f_left;
+            left = upperbound - ratio * (upperbound - lowerbound);
+            f_left = value(left);
+        } else {
+            lowerbound = left;
+            left = right;
+            f_left = f_right;
+            right = lowerbound + ratio * (upperbound - lowerbound);
+            f_right = value(right);
+        }
+    }
+    (lowerbound + upperbound) / 2.0
+}
+
+#[allow(clippy::type_complexity)]
+pub fn dormand_prince(
+    f: &impl Fn(f64, &[f64]) -> Vec<f64>, // dy/dx = f(x, y)
+    x_start: f64,
+    y_start: &[f64],
+    x_end: f64,
+    tolerance: Option<f64>,
+    max_step: Option<f64>,
+) -> Result<(Vec<f64>, Vec<Vec<f64>>), IsentropicFlowError> {
+    // adaptive 5th order runge-kutta with an embedded 4th order error
+    // estimate, returns every accepted step from x_start to x_end, or an error
+    // once the step has to shrink to nothing, usually because f is not finite
+    let tolerance = tolerance.unwrap_or(1e-9);
+    let max_step = max_step.unwrap_or((x_end - x_start).abs()).abs();
+    let direction: f64 = (x_end - x_start).signum();
+
+    // butcher tableau
+    const C: [f64; 7] = [0.0, 1.0 / 5.0, 3.0 / 10.0, 4.0 / 5.0, 8.0 / 9.0, 1.0, 1.0];
+    const A: [[f64; 6]; 7] = [
+        [0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        [1.0 / 5.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0],
+        [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0],
+        [19372.0 / 6561.0, -25360.0 / 2187.0, 64448.0 / 6561.0, -212.0 / 729.0, 0.0, 0.0],
+        [9017.0 / 3168.0, -355.0 / 33.0, 46732.0 / 5247.0, 49.0 / 176.0, -5103.0 / 18656.0, 0.0],
+        [35.0 / 384.0, 0.0, 500.0 / 1113.0, 125.0 / 192.0, -2187.0 / 6784.0, 11.0 / 84.0],
+    ];
+    const B: [f64; 7] = [35.0 / 384.0, 0.0, 500.0 / 1113.0, 125.0 / 192.0, -2187.0 / 6784.0, 11.0 / 84.0, 0.0];
+    const E: [f64; 7] = [
+        71.0 / 57600.0, 0.0, -71.0 / 16695.0, 71.0 / 1920.0, -17253.0 / 339200.0, 22.0 / 525.0, -1.0 / 40.0,
+    ];
+
+    let mut xs: Vec<f64> = vec![x_start];
+    let mut ys: Vec<Vec<f64>> = vec![y_start.to_vec()];
+    let mut x: f64 = x_start;
+    let mut y: Vec<f64> = y_start.to_vec();
+    let mut step: f64 = max_step.min(1e-3 * (x_end - x_start).abs());
+
+    while direction * (x_end - x) > 0.0 {
+        step = step.min((x_end - x).abs());
+        if step < 1e-14 * x.abs().max(1.0) {
+            return Err(IsentropicFlowError::MathError);
+        }
+        let h: f64 = direction * step;
+
+        let mut k: Vec<Vec<f64>> = Vec::with_capacity(7);
+        for i in 0..7 {
+            let stage: Vec<f64> = (0..y.len())
+                .map(|n| y[n] + h * (0..i).map(|j| A[i][j] * k[j][n]).sum::<f64>())
+                .collect();
+            k.push(f(x + C[i] * h, &stage));
+        }
+        let y_next: Vec<f64> = (0..y.len()).map(|n| y[n] + h * (0..7).map(|i| B[i] * k[i][n]).sum::<f64>()).collect();
+
+        // scaled rms error against a mixed absolute and relative tolerance
+        let error: f64 = ((0..y.len())
+            .map(|n| {
+                let estimate = h * (0..7).map(|i| E[i] * k[i][n]).sum::<f64>();
+                let scale = tolerance * (1.0 + y[n].abs().max(y_next[n].abs()));
+                (estimate / scale).powi(2)
+            })
+            .sum::<f64>() / y.len() as f64)
+            .sqrt();
+
+        if error <= 1.0 && y_next.iter().all(|value| value.is_finite()) {
+            x += h;
+            y = y_next;
+            xs.push(x);
+            ys.push(y.clone());
+        }
+        // standard step controller with a safety factor
+        let factor: f64 = if error.is_finite() { (0.9 * error.powf(-0.2)).clamp(0.2, 5.0) } else { 0.2 };
+        step = (step * factor).min(max_step);
+    }
+    Ok((xs, ys))
+}
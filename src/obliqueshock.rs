@@ -2,7 +2,9 @@
 
 use std::f64::consts::PI;
 use crate::isentropic::{valid_specific_heat_ratio, IsentropicFlowError}; 
+use crate::normalshock;
 use crate::numerics::bisection;
+use crate::gas::GasProperties;
 
 
 pub enum Input {
@@ -10,6 +12,7 @@ pub enum Input {
     NormalUpstreamMach(f64),
     DeflectionAngle(f64), // potentially need to change to allow for strong or weak oblique shocks, currently only does weak shocks
     ShockAngle(f64),
+    EntropyChange(f64),
 }
 
 pub enum Output {
@@ -22,20 +25,30 @@ pub enum Output {
     StagnationPressureRatio,
     NormalUpstreamMach,
     NormalDownstreamMach,
+    EntropyChange,
+    StagnationTemperatureRatio,
+    VelocityRatio,
+    InternalEnergyChange,
+    EnthalpyChange,
 }
 
 #[derive(Debug)]
 pub struct ObliqueShock {
-    upstream_mach: f64,             // M1
-    downstream_mach: f64,           // M2
-    deflection_angle: f64,          // θ
-    shock_angle: f64,               // β
-    pressure_ratio: f64,            // p2 / p1
-    density_ratio: f64,             // ρ2 / ρ1
-    temperature_ratio: f64,         // T2 / T1
-    stagnation_pressure_ratio: f64, // p02 / p01
-    normal_upstream_mach: f64,      // M1n
-    normal_downstream_mach: f64,    // M2n
+    pub(crate) upstream_mach: f64,                // M1
+    pub(crate) downstream_mach: f64,              // M2
+    pub(crate) deflection_angle: f64,             // θ
+    pub(crate) shock_angle: f64,                  // β
+    pub(crate) pressure_ratio: f64,               // p2 / p1
+    pub(crate) density_ratio: f64,                // ρ2 / ρ1
+    pub(crate) temperature_ratio: f64,            // T2 / T1
+    pub(crate) stagnation_pressure_ratio: f64,    // p02 / p01
+    pub(crate) normal_upstream_mach: f64,         // M1n
+    pub(crate) normal_downstream_mach: f64,       // M2n
+    pub(crate) entropy_change: f64,               // (s2 - s1) / R
+    pub(crate) stagnation_temperature_ratio: f64, // T02 / T01
+    pub(crate) velocity_ratio: f64,               // V2 / V1
+    pub(crate) internal_energy_change: f64,       // (e2 - e1) / (p1 v1), hugoniot energy term
+    pub(crate) enthalpy_change: f64,              // (h2 - h1) / (p1 v1)
 }
 
 impl ObliqueShock {
@@ -48,6 +61,12 @@ impl ObliqueShock {
         let stagnation_pressure_ratio: f64 = calc_stagnation_pressure_ratio(upstream_mach, shock_angle, specific_heat_ratio)?;
         let normal_upstream_mach: f64 = calc_normal_upstream_mach(upstream_mach, shock_angle)?;
         let normal_downstream_mach: f64 = calc_normal_downstream_mach(downstream_mach, shock_angle, deflection_angle)?;
+        // the normal component sees a normal shock, the tangential one is unchanged
+        let entropy_change: f64 = normalshock::calc_entropy_change_from_upstream_mach(normal_upstream_mach, specific_heat_ratio)?;
+        let stagnation_temperature_ratio: f64 = normalshock::calc_stagnation_temperature_ratio_from_upstream_mach(normal_upstream_mach, specific_heat_ratio)?;
+        let velocity_ratio: f64 = calc_velocity_ratio(upstream_mach, shock_angle, specific_heat_ratio)?;
+        let internal_energy_change: f64 = normalshock::calc_internal_energy_change_from_upstream_mach(normal_upstream_mach, specific_heat_ratio)?;
+        let enthalpy_change: f64 = normalshock::calc_enthalpy_change_from_upstream_mach(normal_upstream_mach, specific_heat_ratio)?;
 
         Ok(ObliqueShock {
             upstream_mach,
@@ -60,6 +79,11 @@ impl ObliqueShock {
             stagnation_pressure_ratio,
             normal_upstream_mach,
             normal_downstream_mach,
+            entropy_change,
+            stagnation_temperature_ratio,
+            velocity_ratio,
+            internal_energy_change,
+            enthalpy_change,
         })
     }
     
@@ -99,6 +123,10 @@ pub fn calculate(input: Vec<Input>, output: Output, specific_heat_ratio: f64) ->
         [Input::UpstreamMach(upstream_mach), Input::NormalUpstreamMach(normal_upstream_mach)] => {
             ObliqueShock::from_mach_and_normal_mach(*upstream_mach, *normal_upstream_mach, specific_heat_ratio)?
         }
+        [Input::UpstreamMach(upstream_mach), Input::EntropyChange(entropy_change)] => {
+            let normal_upstream_mach = normalshock::calc_upstream_mach_from_entropy_change(*entropy_change, specific_heat_ratio)?;
+            ObliqueShock::from_mach_and_normal_mach(*upstream_mach, normal_upstream_mach, specific_heat_ratio)?
+        }
         _ => {
             return Err(IsentropicFlowError::WhatTheFuck);
         }
@@ -114,9 +142,19 @@ pub fn calculate(input: Vec<Input>, output: Output, specific_heat_ratio: f64) ->
         Output::StagnationPressureRatio => Ok(oblique_shock.stagnation_pressure_ratio),
         Output::NormalUpstreamMach => Ok(oblique_shock.normal_upstream_mach),
         Output::NormalDownstreamMach => Ok(oblique_shock.normal_downstream_mach),
+        Output::EntropyChange => Ok(oblique_shock.entropy_change),
+        Output::StagnationTemperatureRatio => Ok(oblique_shock.stagnation_temperature_ratio),
+        Output::VelocityRatio => Ok(oblique_shock.velocity_ratio),
+        Output::InternalEnergyChange => Ok(oblique_shock.internal_energy_change),
+        Output::EnthalpyChange => Ok(oblique_shock.enthalpy_change),
     }
 }
 
+pub fn calculate_for_gas(input: Vec<Input>, output: Output, gas: GasProperties) -> Result<f64, IsentropicFlowError> {
+    // same as calculate with γ taken from a catalogue gas
+    calculate(input, output, gas.specific_heat_ratio)
+}
+
 fn calc_downstream_mach(upstream_mach: f64, shock_angle: f64, deflection_angle: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
     let normal_upstream_mach: f64 = calc_normal_upstream_mach(upstream_mach, shock_angle)?;
 
@@ -181,6 +219,13 @@ pub fn calc_stagnation_pressure_ratio(upstream_mach: f64, shock_angle: f64, spec
     Ok(stagnation_pressure_ratio)
 }
 
+pub fn calc_velocity_ratio(upstream_mach: f64, shock_angle: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
+    // V2 / V1, the tangential component is kept and the normal one drops by ρ1 / ρ2
+    let density_ratio: f64 = calc_density_ratio(upstream_mach, shock_angle, specific_heat_ratio)?;
+    let velocity_ratio: f64 = (shock_angle.cos().powi(2) + (shock_angle.sin() / density_ratio).powi(2)).sqrt();
+    Ok(velocity_ratio)
+}
+
 pub fn calc_shock_angle(upstream_mach: f64, deflection_angle: f64, specific_heat_ratio: f64) -> Result<f64, IsentropicFlowError> {
     if upstream_mach <= 1.0 {
         return Err(IsentropicFlowError::InvalidMachNumber);
@@ -190,7 +235,7 @@ pub fn calc_shock_angle(upstream_mach: f64, deflection_angle: f64, specific_heat
             Ok(value) => value,
             Err(_) => panic!("erm what"),
         };
-        return calculated_deflection_angle - deflection_angle
+        calculated_deflection_angle - deflection_angle
     };
 
     let lower_bound: f64 = deflection_angle;
@@ -215,15 +260,15 @@ pub fn calc_max_shock_angle(upstream_mach: f64, specific_heat_ratio: f64) -> Res
     
     let sin_max_shock_angle: f64 = 
         ((1.0 / (specific_heat_ratio * upstream_mach.powi(2))) * 
-        (1.0 +
+        ((specific_heat_ratio + 1.0) * upstream_mach.powi(2) / 4.0 - 1.0 +
             ((specific_heat_ratio + 1.0) * (
                 (specific_heat_ratio + 1.0) * upstream_mach.powi(4) / 16.0 +
                 (specific_heat_ratio - 1.0) * upstream_mach.powi(2) / 2.0 +
                 1.0
-            ).sqrt())
+            )).sqrt()
         )).sqrt();
 
-    if sin_max_shock_angle > 1.0 || sin_max_shock_angle < 0.0 {
+    if !(0.0..=1.0).contains(&sin_max_shock_angle) {
         return Err(IsentropicFlowError::MathError);
     }
 
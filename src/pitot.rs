@@ -0,0 +1,54 @@
+use crate::isentropic::{self, valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::normalshock::NormalShock;
+use crate::numerics::bisection;
+
+
+pub fn calc_pitot_pressure_ratio_from_mach(mach_number: f64, specific_heat_ratio: f64) -> Result<f64, CompressibleFlowError> {
+    // pitot pressure over freestream static pressure, isentropic stagnation
+    // below mach 1 and the rayleigh pitot formula above it, where the probe
+    // reads the stagnation pressure behind a normal shock
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    if mach_number < 0.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    let stagnation_pressure_ratio: f64 = 1.0 / isentropic::calc_pressure_ratio_from_mach(mach_number, specific_heat_ratio)?;
+    if mach_number <= 1.0 {
+        return Ok(stagnation_pressure_ratio);
+    }
+    let normal_shock: NormalShock = NormalShock::from_upstream_mach(mach_number, specific_heat_ratio)?;
+    Ok(normal_shock.stagnation_pressure_ratio * stagnation_pressure_ratio)
+}
+
+pub fn calc_mach_from_pitot_pressure_ratio(pitot_pressure_ratio: f64, specific_heat_ratio: f64) -> Result<f64, CompressibleFlowError> {
+    // inverse of the above, the reading at mach 1 decides between the
+    // isentropic relation and the rayleigh pitot formula
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    if pitot_pressure_ratio < 1.0 {
+        return Err(IsentropicFlowError::InvalidPressureRatio.into());
+    }
+    let sonic_pitot_pressure_ratio: f64 = calc_pitot_pressure_ratio_from_mach(1.0, specific_heat_ratio)?;
+    if pitot_pressure_ratio <= sonic_pitot_pressure_ratio {
+        return Ok(isentropic::calc_mach_from_pressure_ratio(1.0 / pitot_pressure_ratio, specific_heat_ratio)?);
+    }
+
+    // the ratio grows like M² so doubling soon brackets the root
+    let f = |mach_number: f64| {
+        match calc_pitot_pressure_ratio_from_mach(mach_number, specific_heat_ratio) {
+            Ok(value) => value - pitot_pressure_ratio,
+            Err(_) => f64::NAN,
+        }
+    };
+    let mut upper_mach: f64 = 2.0;
+    while f(upper_mach) < 0.0 {
+        upper_mach *= 2.0;
+        if upper_mach > 1e6 {
+            return Err(IsentropicFlowError::MathError.into());
+        }
+    }
+    Ok(bisection(&f, 1.0, upper_mach, Some(1e-12), None))
+}
@@ -0,0 +1,302 @@
+use std::f64::consts::SQRT_2;
+use crate::isentropic::IsentropicFlowError;
+use crate::error::CompressibleFlowError;
+use crate::gas::UNIVERSAL_GAS_CONSTANT;
+use crate::numerics::bisection;
+
+
+// cubic equations of state written as p = R T / (v - b) - A(T) / D(v) with v
+// the specific volume, which gives the caloric properties from the departure
+// integrals I(v) = ∫∞^v dv / D
+//     e = cv∞ T + (A - T A') I
+//     s = cv∞ ln T + R ln(v - b) - A' I
+// the ideal gas heat capacity is constant and only the vapour root is used,
+// states inside the vapour dome are metastable vapour rather than two phase
+
+#[derive(Debug, Clone, Copy)]
+pub enum CubicEquationOfState {
+    VanDerWaals,  // D = v², A = a
+    PengRobinson, // D = v² + 2 b v - b², A = a α(T)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CubicGas {
+    pub equation_of_state: CubicEquationOfState,
+    pub molar_mass: f64,              // kg/mol
+    pub critical_temperature: f64,    // K
+    pub critical_pressure: f64,       // Pa
+    pub acentric_factor: f64,         // ω, peng robinson only
+    pub ideal_heat_capacity: f64,     // cv∞ / R
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RealGasState {
+    pub temperature: f64,            // K
+    pub pressure: f64,               // Pa
+    pub density: f64,                // kg/m³
+    pub compressibility_factor: f64, // Z = p v / (R T)
+    pub enthalpy: f64,               // J/kg
+    pub entropy: f64,                // J/(kg K)
+    pub speed_of_sound: f64,         // m/s
+    pub fundamental_derivative: f64, // Γ = 1 + (ρ / c) (∂c/∂ρ)s
+    pub velocity: f64,               // m/s
+    pub mach_number: f64,
+}
+
+#[derive(Debug)]
+pub struct RealGasShock {
+    pub upstream: RealGasState,
+    pub downstream: RealGasState, // velocities relative to the shock
+    pub pressure_ratio: f64,      // p2 / p1
+    pub density_ratio: f64,       // ρ2 / ρ1
+    pub temperature_ratio: f64,   // T2 / T1
+    pub entropy_change: f64,      // (s2 - s1) / R, negative means the shock is inadmissible
+}
+
+impl RealGasState {
+    pub fn is_non_classical(&self) -> bool {
+        // Γ < 0, expansion shocks and compression fans are possible (BZT region)
+        self.fundamental_derivative < 0.0
+    }
+}
+
+impl CubicGas {
+    pub fn gas_constant(&self) -> f64 {
+        UNIVERSAL_GAS_CONSTANT / self.molar_mass
+    }
+
+    fn constants(&self) -> (f64, f64) {
+        // (a, b) from the critical point
+        let r: f64 = self.gas_constant();
+        match self.equation_of_state {
+            CubicEquationOfState::VanDerWaals => (
+                27.0 * (r * self.critical_temperature).powi(2) / (64.0 * self.critical_pressure),
+                r * self.critical_temperature / (8.0 * self.critical_pressure),
+            ),
+            CubicEquationOfState::PengRobinson => (
+                0.45724 * (r * self.critical_temperature).powi(2) / self.critical_pressure,
+                0.07780 * r * self.critical_temperature / self.critical_pressure,
+            ),
+        }
+    }
+
+    fn attraction(&self, temperature: f64) -> (f64, f64, f64) {
+        // (A, A', A'') with A = a ψ², ψ = 1 + κ (1 - √(T / Tc)) for peng robinson
+        let (a, _) = self.constants();
+        match self.equation_of_state {
+            CubicEquationOfState::VanDerWaals => (a, 0.0, 0.0),
+            CubicEquationOfState::PengRobinson => {
+                let omega: f64 = self.acentric_factor;
+                let kappa: f64 = 0.37464 + 1.54226 * omega - 0.26992 * omega.powi(2);
+                let root: f64 = (temperature * self.critical_temperature).sqrt();
+                let psi: f64 = 1.0 + kappa * (1.0 - (temperature / self.critical_temperature).sqrt());
+                let dpsi: f64 = -kappa / (2.0 * root);
+                let d2psi: f64 = kappa / (4.0 * temperature * root);
+                (a * psi.powi(2), 2.0 * a * psi * dpsi, 2.0 * a * (dpsi.powi(2) + psi * d2psi))
+            }
+        }
+    }
+
+    fn denominator(&self, volume: f64) -> (f64, f64, f64) {
+        // (D, D', I)
+        let (_, b) = self.constants();
+        match self.equation_of_state {
+            CubicEquationOfState::VanDerWaals => (volume.powi(2), 2.0 * volume, -1.0 / volume),
+            CubicEquationOfState::PengRobinson => (
+                volume.powi(2) + 2.0 * b * volume - b.powi(2),
+                2.0 * volume + 2.0 * b,
+                ((volume + (1.0 - SQRT_2) * b) / (volume + (1.0 + SQRT_2) * b)).ln() / (2.0 * SQRT_2 * b),
+            ),
+        }
+    }
+
+    fn calc_pressure(&self, temperature: f64, volume: f64) -> f64 {
+        let (_, b) = self.constants();
+        let (attraction, _, _) = self.attraction(temperature);
+        let (denominator, _, _) = self.denominator(volume);
+        self.gas_constant() * temperature / (volume - b) - attraction / denominator
+    }
+
+    fn calc_energy(&self, temperature: f64, volume: f64) -> f64 {
+        let (attraction, attraction_derivative, _) = self.attraction(temperature);
+        let (_, _, integral) = self.denominator(volume);
+        self.ideal_heat_capacity * self.gas_constant() * temperature + (attraction - temperature * attraction_derivative) * integral
+    }
+
+    fn calc_entropy(&self, temperature: f64, volume: f64) -> f64 {
+        let (_, b) = self.constants();
+        let (_, attraction_derivative, _) = self.attraction(temperature);
+        let (_, _, integral) = self.denominator(volume);
+        let r: f64 = self.gas_constant();
+        self.ideal_heat_capacity * r * temperature.ln() + r * (volume - b).ln() - attraction_derivative * integral
+    }
+
+    fn calc_sound_speed_squared(&self, temperature: f64, volume: f64) -> f64 {
+        // c² = -v² (∂p/∂v)s = -v² ((∂p/∂v)T - T (∂p/∂T)v² / cv)
+        let (_, b) = self.constants();
+        let r: f64 = self.gas_constant();
+        let (attraction, attraction_derivative, attraction_second_derivative) = self.attraction(temperature);
+        let (denominator, denominator_derivative, integral) = self.denominator(volume);
+        let dp_dt: f64 = r / (volume - b) - attraction_derivative / denominator;
+        let dp_dv: f64 = -r * temperature / (volume - b).powi(2) + attraction * denominator_derivative / denominator.powi(2);
+        let heat_capacity: f64 = self.ideal_heat_capacity * r - temperature * attraction_second_derivative * integral;
+        -volume.powi(2) * (dp_dv - temperature * dp_dt.powi(2) / heat_capacity)
+    }
+
+    fn calc_fundamental_derivative(&self, temperature: f64, volume: f64) -> f64 {
+        // Γ = 1 - (v / c) (∂c/∂v)s, differentiated along the isentrope with
+        // (∂T/∂v)s = -T (∂p/∂T)v / cv
+        let (_, b) = self.constants();
+        let r: f64 = self.gas_constant();
+        let (_, attraction_derivative, attraction_second_derivative) = self.attraction(temperature);
+        let (denominator, _, integral) = self.denominator(volume);
+        let dp_dt: f64 = r / (volume - b) - attraction_derivative / denominator;
+        let heat_capacity: f64 = self.ideal_heat_capacity * r - temperature * attraction_second_derivative * integral;
+        let dt_dv: f64 = -temperature * dp_dt / heat_capacity;
+
+        let step: f64 = 1e-5 * (volume - b);
+        let sound_speed = |v: f64| self.calc_sound_speed_squared(temperature + dt_dv * (v - volume), v).sqrt();
+        let dc_dv: f64 = (sound_speed(volume + step) - sound_speed(volume - step)) / (2.0 * step);
+        1.0 - volume / sound_speed(volume) * dc_dv
+    }
+
+    fn calc_volume(&self, temperature: f64, pressure: f64) -> Result<f64, CompressibleFlowError> {
+        // largest real root of the cubic in Z = p v / (R T)
+        if temperature <= 0.0 {
+            return Err(IsentropicFlowError::InvalidTemperatureRatio.into());
+        }
+        if pressure <= 0.0 {
+            return Err(IsentropicFlowError::InvalidPressureRatio.into());
+        }
+        let rt: f64 = self.gas_constant() * temperature;
+        let (_, b) = self.constants();
+        let (attraction, _, _) = self.attraction(temperature);
+        let a_z: f64 = attraction * pressure / rt.powi(2);
+        let b_z: f64 = b * pressure / rt;
+        let (c2, c1, c0) = match self.equation_of_state {
+            CubicEquationOfState::VanDerWaals => (-(1.0 + b_z), a_z, -a_z * b_z),
+            CubicEquationOfState::PengRobinson => (-(1.0 - b_z), a_z - 3.0 * b_z.powi(2) - 2.0 * b_z, -(a_z * b_z - b_z.powi(2) - b_z.powi(3))),
+        };
+        let compressibility_factor: f64 = calc_largest_cubic_root(c2, c1, c0);
+        if compressibility_factor <= b_z || !compressibility_factor.is_finite() {
+            return Err(IsentropicFlowError::MathError.into());
+        }
+        Ok(compressibility_factor * rt / pressure)
+    }
+
+    fn calc_state(&self, temperature: f64, volume: f64, velocity: f64) -> RealGasState {
+        let pressure: f64 = self.calc_pressure(temperature, volume);
+        let speed_of_sound: f64 = self.calc_sound_speed_squared(temperature, volume).sqrt();
+        RealGasState {
+            temperature,
+            pressure,
+            density: 1.0 / volume,
+            compressibility_factor: pressure * volume / (self.gas_constant() * temperature),
+            enthalpy: self.calc_energy(temperature, volume) + pressure * volume,
+            entropy: self.calc_entropy(temperature, volume),
+            speed_of_sound,
+            fundamental_derivative: self.calc_fundamental_derivative(temperature, volume),
+            velocity,
+            mach_number: velocity / speed_of_sound,
+        }
+    }
+
+    pub fn state(&self, temperature: f64, pressure: f64) -> Result<RealGasState, CompressibleFlowError> {
+        // gas at rest
+        Ok(self.calc_state(temperature, self.calc_volume(temperature, pressure)?, 0.0))
+    }
+
+    pub fn isentropic_expansion(&self, stagnation_temperature: f64, stagnation_pressure: f64, pressure: f64) -> Result<RealGasState, CompressibleFlowError> {
+        // static state at the given pressure on the stagnation isentrope, the
+        // velocity from h0 = h + u² / 2
+        if pressure <= 0.0 || pressure > stagnation_pressure {
+            return Err(IsentropicFlowError::InvalidPressureRatio.into());
+        }
+        let stagnation: RealGasState = self.state(stagnation_temperature, stagnation_pressure)?;
+        let r: f64 = self.gas_constant();
+        let g = |temperature_ratio: f64| match self.calc_volume(temperature_ratio * stagnation_temperature, pressure) {
+            Ok(volume) => (self.calc_entropy(temperature_ratio * stagnation_temperature, volume) - stagnation.entropy) / r,
+            Err(_) => f64::NAN,
+        };
+        let temperature: f64 = stagnation_temperature * bisection(&g, 1e-3, 1.0, Some(1e-13), None);
+        let volume: f64 = self.calc_volume(temperature, pressure)?;
+        let kinetic_energy: f64 = stagnation.enthalpy - (self.calc_energy(temperature, volume) + pressure * volume);
+        Ok(self.calc_state(temperature, volume, (2.0 * kinetic_energy.max(0.0)).sqrt()))
+    }
+}
+
+impl RealGasShock {
+    pub fn from_upstream_mach(gas: CubicGas, upstream_mach: f64, upstream_temperature: f64, upstream_pressure: f64) -> Result<RealGasShock, CompressibleFlowError> {
+        // rankine hugoniot with p2 = p1 + m² (v1 - v2) and h2 = h1 + m² (v1² - v2²) / 2,
+        // the downstream volume is scanned from v1 towards b for the
+        // compression root that is not the trivial v2 = v1
+        if upstream_mach <= 0.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        let (_, b) = gas.constants();
+        let upstream_volume: f64 = gas.calc_volume(upstream_temperature, upstream_pressure)?;
+        let upstream_state: RealGasState = gas.calc_state(upstream_temperature, upstream_volume, 0.0);
+        let upstream_velocity: f64 = upstream_mach * upstream_state.speed_of_sound;
+        let upstream: RealGasState = gas.calc_state(upstream_temperature, upstream_volume, upstream_velocity);
+        let mass_flux: f64 = upstream_velocity / upstream_volume;
+
+        // temperature with p(T, v) = p, pressure rises with temperature at fixed volume
+        let temperature = |volume: f64, pressure: f64| -> Option<f64> {
+            let mut upper: f64 = upstream_temperature;
+            while gas.calc_pressure(upper, volume) < pressure {
+                upper *= 2.0;
+                if upper > 1e3 * upstream_temperature {
+                    return None;
+                }
+            }
+            let g = |temperature_ratio: f64| (gas.calc_pressure(temperature_ratio * upper, volume) - pressure) / pressure;
+            Some(upper * bisection(&g, 1e-6, 1.0, Some(1e-14), None))
+        };
+        let residual = |volume: f64| -> f64 {
+            let pressure: f64 = upstream_pressure + mass_flux.powi(2) * (upstream_volume - volume);
+            match temperature(volume, pressure) {
+                Some(temperature) => {
+                    let enthalpy: f64 = gas.calc_energy(temperature, volume) + pressure * volume;
+                    (enthalpy - upstream.enthalpy - mass_flux.powi(2) * (upstream_volume.powi(2) - volume.powi(2)) / 2.0) / upstream_velocity.powi(2)
+                }
+                None => f64::NAN,
+            }
+        };
+
+        // geometric scan of v1 - v over (0, v1 - b)
+        let points: usize = 400;
+        let volume_at = |i: usize| upstream_volume - (upstream_volume - b) * 1e-8_f64.powf(1.0 - i as f64 / points as f64) * (1.0 - 1e-9);
+        let initial_sign: f64 = residual(volume_at(0)).signum();
+        let crossing: usize = (1..=points)
+            .find(|&i| residual(volume_at(i)).signum() != initial_sign)
+            .ok_or(IsentropicFlowError::InvalidMachNumber)?;
+        let g = |x: f64| residual(x);
+        let volume: f64 = bisection(&g, volume_at(crossing - 1), volume_at(crossing), Some(1e-14), None);
+        let pressure: f64 = upstream_pressure + mass_flux.powi(2) * (upstream_volume - volume);
+        let downstream_temperature: f64 = temperature(volume, pressure).ok_or(IsentropicFlowError::MathError)?;
+        let downstream: RealGasState = gas.calc_state(downstream_temperature, volume, mass_flux * volume);
+
+        Ok(RealGasShock {
+            upstream,
+            downstream,
+            pressure_ratio: downstream.pressure / upstream.pressure,
+            density_ratio: downstream.density / upstream.density,
+            temperature_ratio: downstream.temperature / upstream.temperature,
+            entropy_change: (downstream.entropy - upstream.entropy) / gas.gas_constant(),
+        })
+    }
+}
+
+fn calc_largest_cubic_root(c2: f64, c1: f64, c0: f64) -> f64 {
+    // z³ + c2 z² + c1 z + c0 = 0 through the depressed cubic t³ + p t + q
+    let p: f64 = c1 - c2.powi(2) / 3.0;
+    let q: f64 = 2.0 * c2.powi(3) / 27.0 - c2 * c1 / 3.0 + c0;
+    let discriminant: f64 = (q / 2.0).powi(2) + (p / 3.0).powi(3);
+    let t: f64 = if discriminant > 0.0 || p == 0.0 {
+        (-q / 2.0 + discriminant.sqrt()).cbrt() + (-q / 2.0 - discriminant.sqrt()).cbrt()
+    } else {
+        let phi: f64 = (3.0 * q / (2.0 * p) * (-3.0 / p).sqrt()).clamp(-1.0, 1.0).acos();
+        2.0 * (-p / 3.0).sqrt() * (phi / 3.0).cos()
+    };
+    t - c2 / 3.0
+}
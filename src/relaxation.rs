@@ -0,0 +1,148 @@
+use crate::isentropic::IsentropicFlowError;
+use crate::error::CompressibleFlowError;
+use crate::normalshock::NormalShock;
+use crate::gas::DiatomicGas;
+use crate::numerics::{bisection, dormand_prince};
+
+
+// translation and rotation are fully excited and equilibrate inside the shock,
+// vibration is frozen through it and relaxes downstream, so the frozen gas has
+// three translational and two rotational degrees of freedom, γ = (f + 2) / f
+const FROZEN_DEGREES_OF_FREEDOM: f64 = 5.0;
+const FROZEN_SPECIFIC_HEAT_RATIO: f64 = (FROZEN_DEGREES_OF_FREEDOM + 2.0) / FROZEN_DEGREES_OF_FREEDOM;
+const ATMOSPHERE: f64 = 101325.0; // Pa
+
+#[derive(Debug, Clone, Copy)]
+pub struct RelaxationState {
+    pub temperature: f64,             // T, K
+    pub vibrational_temperature: f64, // Tv, K
+    pub pressure: f64,                // Pa
+    pub density: f64,                 // kg/m³
+    pub velocity: f64,                // m/s relative to the shock
+}
+
+#[derive(Debug)]
+pub struct VibrationalRelaxation {
+    pub frozen_state: RelaxationState,      // just behind the shock
+    pub equilibrium_state: RelaxationState, // far downstream, T = Tv
+    pub distance: Vec<f64>,                 // m behind the shock
+    pub temperature: Vec<f64>,
+    pub vibrational_temperature: Vec<f64>,
+    pub pressure: Vec<f64>,
+    pub density: Vec<f64>,
+    pub velocity: Vec<f64>,
+}
+
+impl VibrationalRelaxation {
+    #[allow(clippy::too_many_arguments)]
+    pub fn behind_normal_shock(
+        gas: DiatomicGas,
+        upstream_mach: f64,        // frozen mach number, with the frozen γ of a diatomic gas
+        upstream_temperature: f64, // K, vibration in equilibrium upstream
+        upstream_pressure: f64,    // Pa
+        length: f64,               // m to integrate behind the shock
+        tolerance: Option<f64>,
+        max_step: Option<f64>,
+    ) -> Result<VibrationalRelaxation, CompressibleFlowError> {
+        // landau teller relaxation dev/dt = (ev(T) - ev) / τ with millikan white
+        // relaxation times, integrated in ev along x with dt = dx / u while mass,
+        // momentum and total enthalpy fluxes stay fixed
+        if upstream_mach <= 1.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        if upstream_temperature <= 0.0 {
+            return Err(IsentropicFlowError::InvalidTemperatureRatio.into());
+        }
+        if upstream_pressure <= 0.0 {
+            return Err(IsentropicFlowError::InvalidPressureRatio.into());
+        }
+        if length <= 0.0 {
+            return Err(CompressibleFlowError::InvalidGeometry);
+        }
+        let r: f64 = gas.gas_constant();
+        let k: f64 = FROZEN_SPECIFIC_HEAT_RATIO / (FROZEN_SPECIFIC_HEAT_RATIO - 1.0);
+        let upstream_velocity: f64 = upstream_mach * (FROZEN_SPECIFIC_HEAT_RATIO * r * upstream_temperature).sqrt();
+        let upstream_density: f64 = upstream_pressure / (r * upstream_temperature);
+        let upstream_vibrational_energy: f64 = gas.vibrational_energy(upstream_temperature);
+
+        let mass_flux: f64 = upstream_density * upstream_velocity;
+        let momentum_flux: f64 = upstream_pressure + mass_flux * upstream_velocity;
+        let total_enthalpy: f64 = k * r * upstream_temperature + upstream_vibrational_energy + upstream_velocity.powi(2) / 2.0;
+
+        // state for a given vibrational energy, p = P - m u and ρ = m / u turn
+        // the energy equation into (1/2 - k) u² + k (P / m) u - (H - ev) = 0,
+        // the subsonic root is the one behind the shock
+        let state = |vibrational_energy: f64| -> Result<RelaxationState, CompressibleFlowError> {
+            let a: f64 = 0.5 - k;
+            let b: f64 = k * momentum_flux / mass_flux;
+            let c: f64 = -(total_enthalpy - vibrational_energy);
+            let discriminant: f64 = b.powi(2) - 4.0 * a * c;
+            if discriminant < 0.0 {
+                return Err(IsentropicFlowError::MathError.into());
+            }
+            let velocity: f64 = (-b + discriminant.sqrt()) / (2.0 * a);
+            let pressure: f64 = momentum_flux - mass_flux * velocity;
+            let density: f64 = mass_flux / velocity;
+            Ok(RelaxationState {
+                temperature: pressure / (density * r),
+                vibrational_temperature: gas.vibrational_temperature_from_energy(vibrational_energy),
+                pressure,
+                density,
+                velocity,
+            })
+        };
+
+        // the frozen state is the perfect gas normal shock
+        let normal_shock: NormalShock = NormalShock::from_upstream_mach(upstream_mach, FROZEN_SPECIFIC_HEAT_RATIO)?;
+        let frozen_state: RelaxationState = RelaxationState {
+            temperature: upstream_temperature * normal_shock.temperature_ratio,
+            vibrational_temperature: upstream_temperature,
+            pressure: upstream_pressure * normal_shock.pressure_ratio,
+            density: upstream_density * normal_shock.density_ratio,
+            velocity: upstream_velocity * normal_shock.velocity_ratio,
+        };
+
+        // equilibrium where the vibrational energy matches the translational temperature
+        let g = |vibrational_energy: f64| match state(vibrational_energy) {
+            Ok(current) => gas.vibrational_energy(current.temperature) - vibrational_energy,
+            Err(_) => f64::NAN,
+        };
+        let mut upper_energy: f64 = gas.vibrational_energy(frozen_state.temperature);
+        while g(upper_energy) > 0.0 {
+            upper_energy *= 2.0;
+        }
+        let equilibrium_energy: f64 = bisection(&g, upstream_vibrational_energy, upper_energy, Some(1e-9 * upper_energy), None);
+        let equilibrium_state: RelaxationState = state(equilibrium_energy)?;
+
+        let f = |_x: f64, y: &[f64]| -> Vec<f64> {
+            match state(y[0]) {
+                Ok(current) => {
+                    let relaxation_time: f64 = calc_millikan_white_relaxation_time(gas, current.temperature, current.pressure);
+                    vec![(gas.vibrational_energy(current.temperature) - y[0]) / (relaxation_time * current.velocity)]
+                }
+                Err(_) => vec![f64::NAN],
+            }
+        };
+        let (distance, solution) = dormand_prince(&f, 0.0, &[upstream_vibrational_energy], length, tolerance, max_step)?;
+        let states: Vec<RelaxationState> = solution.iter().map(|y| state(y[0])).collect::<Result<_, _>>()?;
+
+        Ok(VibrationalRelaxation {
+            frozen_state,
+            equilibrium_state,
+            distance,
+            temperature: states.iter().map(|current| current.temperature).collect(),
+            vibrational_temperature: states.iter().map(|current| current.vibrational_temperature).collect(),
+            pressure: states.iter().map(|current| current.pressure).collect(),
+            density: states.iter().map(|current| current.density).collect(),
+            velocity: states.iter().map(|current| current.velocity).collect(),
+        })
+    }
+}
+
+pub fn calc_millikan_white_relaxation_time(gas: DiatomicGas, temperature: f64, pressure: f64) -> f64 {
+    // p τ = exp(A (T^-1/3 - 0.015 μ^1/4) - 18.42) atm s, A = 1.16e-3 μ^1/2 θv^4/3
+    // with μ the reduced mass of a self collision in g/mol
+    let reduced_mass: f64 = gas.molar_mass * 1e3 / 2.0;
+    let a: f64 = 1.16e-3 * reduced_mass.sqrt() * gas.vibrational_temperature.powf(4.0 / 3.0);
+    (a * (temperature.powf(-1.0 / 3.0) - 0.015 * reduced_mass.powf(0.25)) - 18.42).exp() / (pressure / ATMOSPHERE)
+}
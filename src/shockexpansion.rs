@@ -0,0 +1,181 @@
+use crate::isentropic::{self, valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::newtonian;
+use crate::obliqueshock::{self, ObliqueShock};
+
+
+#[derive(Debug, Clone, Copy)]
+pub struct AirfoilPanel {
+    // panels run from the leading edge towards the trailing edge on each surface
+    pub start: (f64, f64),
+    pub end: (f64, f64),
+    pub mach_number: Option<f64>,  // M on the panel, none where the method gives no valid M
+    pub pressure_ratio: f64,       // p / p∞
+    pub pressure_coefficient: f64, // Cp = (p - p∞) / q∞
+    pub detached: bool,            // behind a detached shock, Cp from modified newtonian
+}
+
+#[derive(Debug)]
+pub struct ShockExpansionAirfoil {
+    // lengths and coefficients are based on the chord, the moment is taken about
+    // the quarter chord and positive nose up
+    pub upper_panels: Vec<AirfoilPanel>,
+    pub lower_panels: Vec<AirfoilPanel>,
+    pub lift_coefficient: f64,
+    pub wave_drag_coefficient: f64,
+    pub moment_coefficient: f64,
+    pub detached_corner: Option<(f64, f64)>, // corner where a shock first detaches
+}
+
+impl ShockExpansionAirfoil {
+    pub fn new(surface: &[(f64, f64)], freestream_mach: f64, angle_of_attack: f64, specific_heat_ratio: f64) -> Result<ShockExpansionAirfoil, CompressibleFlowError> {
+        // surface is a closed polyline in selig order, from the trailing edge over
+        // the upper surface to the leading edge (smallest x) and back along the
+        // lower surface, the freestream meets the chord line at angle_of_attack
+        // and each surface is marched from the leading edge with an oblique shock
+        // at every compression corner and a prandtl meyer fan at every expansion
+        if !valid_specific_heat_ratio(specific_heat_ratio) {
+            return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+        }
+        if freestream_mach <= 1.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        let (upper_surface, lower_surface) = split_surface(surface)?;
+
+        // compression is positive, on the upper surface it is the wall turning
+        // up into the stream and on the lower surface turning down
+        let (upper_panels, upper_detachment) = march_surface(&upper_surface, freestream_mach, |angle| angle - angle_of_attack, specific_heat_ratio)?;
+        let (lower_panels, lower_detachment) = march_surface(&lower_surface, freestream_mach, |angle| angle_of_attack - angle, specific_heat_ratio)?;
+
+        let chord: f64 = surface[0].0 - upper_surface[0].0;
+        let reference: (f64, f64) = (upper_surface[0].0 + 0.25 * chord, upper_surface[0].1);
+        let (lift_coefficient, wave_drag_coefficient, moment_coefficient) =
+            calc_force_coefficients(&upper_panels, &lower_panels, angle_of_attack, chord, reference);
+
+        Ok(ShockExpansionAirfoil {
+            upper_panels,
+            lower_panels,
+            lift_coefficient,
+            wave_drag_coefficient,
+            moment_coefficient,
+            detached_corner: upper_detachment.or(lower_detachment),
+        })
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn split_surface(surface: &[(f64, f64)]) -> Result<(Vec<(f64, f64)>, Vec<(f64, f64)>), CompressibleFlowError> {
+    // upper and lower surfaces from the leading edge to the trailing edge
+    if surface.len() < 3 {
+        return Err(CompressibleFlowError::InvalidGeometry);
+    }
+    let leading_edge: usize = (0..surface.len())
+        .min_by(|&a, &b| surface[a].0.total_cmp(&surface[b].0))
+        .ok_or(CompressibleFlowError::InvalidGeometry)?;
+    if leading_edge == 0 || leading_edge == surface.len() - 1 {
+        return Err(CompressibleFlowError::InvalidGeometry);
+    }
+    let upper_surface: Vec<(f64, f64)> = surface[..=leading_edge].iter().rev().copied().collect();
+    let lower_surface: Vec<(f64, f64)> = surface[leading_edge..].to_vec();
+    for side in [&upper_surface, &lower_surface] {
+        if side.windows(2).any(|pair| pair[1].0 <= pair[0].0) {
+            return Err(CompressibleFlowError::InvalidGeometry);
+        }
+    }
+    Ok((upper_surface, lower_surface))
+}
+
+#[allow(clippy::type_complexity)]
+fn march_surface(side: &[(f64, f64)], freestream_mach: f64, deflection: impl Fn(f64) -> f64, specific_heat_ratio: f64) -> Result<(Vec<AirfoilPanel>, Option<(f64, f64)>), CompressibleFlowError> {
+    // shock expansion theory has no answer once the flow behind a corner is
+    // subsonic, from the first detached corner on the panels take modified
+    // newtonian Cp = Cp,max sin² δ with the deflection δ from the freestream,
+    // zero in the shadow of the flow, and have no mach number
+    let mut panels: Vec<AirfoilPanel> = Vec::with_capacity(side.len() - 1);
+    let mut mach_number: f64 = freestream_mach;
+    let mut pressure_ratio: f64 = 1.0;
+    let mut previous_deflection: f64 = 0.0;
+    let mut detached_corner: Option<(f64, f64)> = None;
+
+    for pair in side.windows(2) {
+        let panel_deflection: f64 = deflection((pair[1].1 - pair[0].1).atan2(pair[1].0 - pair[0].0));
+        let turning_angle: f64 = panel_deflection - previous_deflection;
+
+        if detached_corner.is_none() && turning_angle > 0.0 {
+            match calc_shock(mach_number, turning_angle, specific_heat_ratio)? {
+                Some(shock) => {
+                    mach_number = shock.downstream_mach;
+                    pressure_ratio *= shock.pressure_ratio;
+                }
+                None => detached_corner = Some(pair[0]),
+            }
+        }
+        if detached_corner.is_some() {
+            let pressure_coefficient: f64 = newtonian::calc_max_pressure_coefficient(freestream_mach, specific_heat_ratio)? * panel_deflection.max(0.0).sin().powi(2);
+            panels.push(AirfoilPanel {
+                start: pair[0],
+                end: pair[1],
+                mach_number: None,
+                pressure_ratio: 1.0 + specific_heat_ratio * freestream_mach.powi(2) / 2.0 * pressure_coefficient,
+                pressure_coefficient,
+                detached: true,
+            });
+            continue;
+        }
+        if turning_angle < 0.0 {
+            let upstream_pressure_ratio: f64 = isentropic::calc_pressure_ratio_from_mach(mach_number, specific_heat_ratio)?;
+            let prandtl_meyer_angle: f64 = isentropic::prandtl_meyer_function(mach_number, specific_heat_ratio)? - turning_angle;
+            mach_number = isentropic::calc_mach_from_prandtl_meyer_angle(prandtl_meyer_angle, specific_heat_ratio)?;
+            pressure_ratio *= isentropic::calc_pressure_ratio_from_mach(mach_number, specific_heat_ratio)? / upstream_pressure_ratio;
+        }
+
+        panels.push(AirfoilPanel {
+            start: pair[0],
+            end: pair[1],
+            mach_number: Some(mach_number),
+            pressure_ratio,
+            pressure_coefficient: 2.0 / (specific_heat_ratio * freestream_mach.powi(2)) * (pressure_ratio - 1.0),
+            detached: false,
+        });
+        previous_deflection = panel_deflection;
+    }
+    Ok((panels, detached_corner))
+}
+
+pub(crate) fn calc_shock(upstream_mach: f64, deflection_angle: f64, specific_heat_ratio: f64) -> Result<Option<ObliqueShock>, CompressibleFlowError> {
+    // weak attached shock, none once the deflection is past the maximum or the
+    // flow behind the shock would be subsonic
+    let max_shock_angle: f64 = obliqueshock::calc_max_shock_angle(upstream_mach, specific_heat_ratio)?;
+    let max_deflection_angle: f64 = obliqueshock::calc_deflection_angle(upstream_mach, max_shock_angle, specific_heat_ratio)?;
+    if deflection_angle >= max_deflection_angle {
+        return Ok(None);
+    }
+    match ObliqueShock::from_mach_and_deflection_angle(upstream_mach, deflection_angle, specific_heat_ratio) {
+        Ok(shock) => Ok(Some(shock)),
+        Err(IsentropicFlowError::InvalidMachNumber) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub(crate) fn calc_force_coefficients(upper_panels: &[AirfoilPanel], lower_panels: &[AirfoilPanel], angle_of_attack: f64, chord: f64, reference: (f64, f64)) -> (f64, f64, f64) {
+    // (cl, cd, cm) from the panel pressure coefficients, pressure acts along
+    // the inward normal and the freestream pressure integrates to zero on a
+    // closed surface
+    let mut axial: f64 = 0.0;
+    let mut normal: f64 = 0.0;
+    let mut moment: f64 = 0.0;
+    for (panels, outward) in [(upper_panels, 1.0), (lower_panels, -1.0)] {
+        for panel in panels {
+            let (dx, dy) = (panel.end.0 - panel.start.0, panel.end.1 - panel.start.1);
+            // outward normal times panel length is (-dy, dx) above and (dy, -dx) below
+            let force: (f64, f64) = (panel.pressure_coefficient * outward * dy, -panel.pressure_coefficient * outward * dx);
+            let centre: (f64, f64) = (0.5 * (panel.start.0 + panel.end.0), 0.5 * (panel.start.1 + panel.end.1));
+            axial += force.0;
+            normal += force.1;
+            moment -= (centre.0 - reference.0) * force.1 - (centre.1 - reference.1) * force.0;
+        }
+    }
+    let lift: f64 = normal * angle_of_attack.cos() - axial * angle_of_attack.sin();
+    let drag: f64 = axial * angle_of_attack.cos() + normal * angle_of_attack.sin();
+    (lift / chord, drag / chord, moment / chord.powi(2))
+}
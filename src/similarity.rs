@@ -0,0 +1,177 @@
+use crate::isentropic::{valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::numerics::{bisection, dormand_prince};
+
+
+// compressible falkner skan equations in illingworth levy variables, with
+// f' = u / ue, g = H / He and C = ρ μ / (ρe μe)
+//     (C f'')' + f f'' + β (T / Te - f'²) = 0
+//     (C g' / Pr)' + f g' + m ((1 - 1 / Pr) C f' f'')' = 0,  m = (γ - 1) Me² / (1 + (γ - 1) Me² / 2)
+// integrated as a first order system in f, f', τ = C f'', g and the energy
+// flux q = C g' / Pr + m (1 - 1 / Pr) f' τ, shooting for τ(0) and q(0) or g(0)
+const DEFAULT_PRANDTL_NUMBER: f64 = 0.71;
+const DEFAULT_EDGE_LENGTH: f64 = 10.0;
+
+#[derive(Debug, Clone, Copy)]
+pub enum ViscosityLaw {
+    ChapmanRubesin(f64),                                               // constant C
+    Sutherland { edge_temperature: f64, sutherland_temperature: f64 }, // K
+}
+
+#[derive(Debug)]
+pub struct SimilarityBoundaryLayer {
+    pub eta: Vec<f64>,               // η
+    pub velocity: Vec<f64>,          // u / ue
+    pub temperature: Vec<f64>,       // T / Te
+    pub wall_temperature_ratio: f64, // Tw / Te, the recovery temperature for an adiabatic wall
+    pub skin_friction: f64,          // cf √Re_x
+    pub stanton_number: Option<f64>, // St √Re_x with St = qw / (ρe ue (He - hw)), none when adiabatic
+}
+
+impl ViscosityLaw {
+    fn calc_chapman_rubesin(&self, temperature_ratio: f64) -> f64 {
+        match *self {
+            ViscosityLaw::ChapmanRubesin(constant) => constant,
+            ViscosityLaw::Sutherland { edge_temperature, sutherland_temperature } => {
+                // ρ / ρe = Te / T at constant pressure across the layer
+                let s: f64 = sutherland_temperature / edge_temperature;
+                temperature_ratio.sqrt() * (1.0 + s) / (temperature_ratio + s)
+            }
+        }
+    }
+}
+
+impl SimilarityBoundaryLayer {
+    pub fn new(
+        edge_mach: f64,
+        wall_temperature_ratio: Option<f64>, // Tw / Te, none for an adiabatic wall
+        pressure_gradient: f64,              // β = 2 m / (m + 1) for ue ∝ x^m
+        viscosity: ViscosityLaw,
+        prandtl_number: Option<f64>,
+        specific_heat_ratio: f64,
+        edge_length: Option<f64>,            // η taken as the edge, 10 by default
+    ) -> Result<SimilarityBoundaryLayer, CompressibleFlowError> {
+        if !valid_specific_heat_ratio(specific_heat_ratio) {
+            return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+        }
+        if edge_mach < 0.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        if !(-0.19..2.0).contains(&pressure_gradient) {
+            // separated below about -0.199 for the incompressible layer
+            return Err(CompressibleFlowError::InvalidFlowRegime);
+        }
+        let prandtl_number: f64 = prandtl_number.unwrap_or(DEFAULT_PRANDTL_NUMBER);
+        if prandtl_number <= 0.0 {
+            return Err(CompressibleFlowError::InvalidFlowRegime);
+        }
+        let edge_length: f64 = edge_length.unwrap_or(DEFAULT_EDGE_LENGTH);
+        if edge_length <= 0.0 {
+            return Err(CompressibleFlowError::InvalidGeometry);
+        }
+        let k: f64 = (specific_heat_ratio - 1.0) / 2.0 * edge_mach.powi(2);
+        let m: f64 = 2.0 * k / (1.0 + k);
+        let temperature_ratio = |velocity: f64, enthalpy: f64| enthalpy * (1.0 + k) - k * velocity.powi(2);
+
+        let f = |_eta: f64, y: &[f64]| -> Vec<f64> {
+            if y[1] < -0.5 || y[1] > 3.0 || y[3].abs() > 50.0 {
+                // a diverging guess, hold it as soon as it leaves the attached
+                // layer so the residual keeps the sign it diverged with
+                return vec![0.0; 5];
+            }
+            // poor guesses can drive T negative on the way out, keep C finite
+            // so the edge velocity still grows with the wall shear
+            let theta: f64 = temperature_ratio(y[1], y[3]).max(1e-2);
+            let c: f64 = viscosity.calc_chapman_rubesin(theta);
+            let shear: f64 = y[2] / c;
+            let enthalpy_gradient: f64 = (y[4] - m * (1.0 - 1.0 / prandtl_number) * y[1] * y[2]) * prandtl_number / c;
+            vec![
+                y[1],
+                shear,
+                -y[0] * shear - pressure_gradient * (theta - y[1].powi(2)),
+                enthalpy_gradient,
+                -y[0] * enthalpy_gradient,
+            ]
+        };
+        let integrate = |wall_shear: f64, wall_enthalpy: f64, wall_flux: f64| {
+            dormand_prince(&f, 0.0, &[0.0, 0.0, wall_shear, wall_enthalpy, wall_flux], edge_length, Some(1e-9), None)
+        };
+        let edge = |wall_shear: f64, wall_enthalpy: f64, wall_flux: f64| -> Vec<f64> {
+            match integrate(wall_shear, wall_enthalpy, wall_flux) {
+                Ok((_, solution)) => solution.last().cloned().unwrap_or_else(|| vec![f64::NAN; 5]),
+                Err(_) => vec![f64::NAN; 5],
+            }
+        };
+
+        // wall shear giving f' = 1 at the edge, for a given wall energy state
+        let solve_shear = |wall_enthalpy: f64, wall_flux: f64| -> Result<f64, CompressibleFlowError> {
+            let residual = |wall_shear: f64| edge(wall_shear, wall_enthalpy, wall_flux)[1] - 1.0;
+            let mut upper: f64 = 1.0;
+            while residual(upper) < 0.0 {
+                upper *= 2.0;
+                if upper > 1e3 {
+                    return Err(CompressibleFlowError::InvalidFlowRegime);
+                }
+            }
+            if residual(0.0) > 0.0 {
+                return Err(CompressibleFlowError::InvalidFlowRegime);
+            }
+            Ok(bisection(&residual, 0.0, upper, Some(1e-10), None))
+        };
+        // outer shooting on g = 1 at the edge, either the wall flux with the
+        // wall enthalpy fixed or the wall enthalpy with no flux
+        let edge_enthalpy = |wall_enthalpy: f64, wall_flux: f64| match solve_shear(wall_enthalpy, wall_flux) {
+            Ok(wall_shear) => edge(wall_shear, wall_enthalpy, wall_flux)[3] - 1.0,
+            Err(_) => f64::NAN,
+        };
+        let bracket = |residual: &dyn Fn(f64) -> f64, start: f64, step: f64| -> Result<(f64, f64), CompressibleFlowError> {
+            // walk both ways from start until the residual changes sign
+            let initial: f64 = residual(start);
+            let mut width: f64 = step;
+            while width < 1e3 {
+                for candidate in [start + width, start - width] {
+                    if residual(candidate) * initial <= 0.0 {
+                        return Ok((start, candidate));
+                    }
+                }
+                width *= 2.0;
+            }
+            Err(CompressibleFlowError::InvalidFlowRegime)
+        };
+
+        let (wall_enthalpy, wall_flux) = match wall_temperature_ratio {
+            Some(ratio) => {
+                if ratio <= 0.0 {
+                    return Err(IsentropicFlowError::InvalidTemperatureRatio.into());
+                }
+                let wall_enthalpy: f64 = ratio / (1.0 + k);
+                let residual = |wall_flux: f64| edge_enthalpy(wall_enthalpy, wall_flux);
+                let (lower, upper) = bracket(&residual, 0.0, 0.1)?;
+                (wall_enthalpy, bisection(&residual, lower, upper, Some(1e-10), None))
+            }
+            None => {
+                let residual = |wall_enthalpy: f64| edge_enthalpy(wall_enthalpy, 0.0);
+                let (lower, upper) = bracket(&residual, 1.0, 0.05)?;
+                (bisection(&residual, lower, upper, Some(1e-9), None), 0.0)
+            }
+        };
+        let wall_shear: f64 = solve_shear(wall_enthalpy, wall_flux)?;
+        let (eta, solution) = integrate(wall_shear, wall_enthalpy, wall_flux)?;
+
+        // for ue ∝ x^m, ξ = ρe μe ue x / (m + 1) and m + 1 = 2 / (2 - β)
+        let scale: f64 = (2.0 - pressure_gradient).sqrt();
+        let stanton_number: Option<f64> = match wall_temperature_ratio {
+            Some(_) if (1.0 - wall_enthalpy).abs() > 1e-12 => Some(wall_flux / ((1.0 - wall_enthalpy) * scale)),
+            _ => None,
+        };
+
+        Ok(SimilarityBoundaryLayer {
+            velocity: solution.iter().map(|y| y[1]).collect(),
+            temperature: solution.iter().map(|y| temperature_ratio(y[1], y[3])).collect(),
+            eta,
+            wall_temperature_ratio: wall_enthalpy * (1.0 + k),
+            skin_friction: 2.0 * wall_shear / scale,
+            stanton_number,
+        })
+    }
+}
@@ -0,0 +1,129 @@
+use crate::isentropic::{self, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::normalshock::NormalShock;
+use crate::airdata::StandardAtmosphere;
+use crate::gas::{self, GasProperties};
+
+
+// convective heating at the stagnation point of a blunt body, SI units, the
+// boundary layer edge is the stagnation state behind the normal part of the
+// bow shock for a calorically perfect gas, so edge temperatures run high once
+// the gas behind the shock would dissociate
+const DEFAULT_PRANDTL_NUMBER: f64 = 0.71;
+const DEFAULT_LEWIS_NUMBER: f64 = 1.4;
+const SUTTON_GRAVES_EARTH: f64 = 1.7415e-4; // kg^1/2 / m, earth air
+
+#[derive(Debug)]
+pub struct StagnationPointHeating {
+    pub stagnation_pressure: f64,     // pe behind the shock, Pa
+    pub stagnation_temperature: f64,  // Te, K
+    pub stagnation_density: f64,      // ρe, kg/m³
+    pub velocity_gradient: f64,       // (due/dx)e, 1/s
+    pub fay_riddell_heat_flux: f64,   // W/m²
+    pub sutton_graves_heat_flux: f64, // W/m²
+}
+
+impl StagnationPointHeating {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        nose_radius: f64,                   // m
+        freestream_velocity: f64,           // m/s
+        freestream_density: f64,            // kg/m³
+        freestream_temperature: f64,        // K
+        wall_temperature: f64,              // K
+        gas: GasProperties,
+        lewis_number: Option<f64>,          // 1.4 by default
+        dissociation_enthalpy: Option<f64>, // hD at the edge, J/kg, 0 by default
+    ) -> Result<StagnationPointHeating, CompressibleFlowError> {
+        if nose_radius <= 0.0 {
+            return Err(CompressibleFlowError::InvalidGeometry);
+        }
+        if freestream_density <= 0.0 {
+            return Err(IsentropicFlowError::InvalidDensityRatio.into());
+        }
+        if freestream_temperature <= 0.0 || wall_temperature <= 0.0 {
+            return Err(IsentropicFlowError::InvalidTemperatureRatio.into());
+        }
+        let specific_heat_ratio: f64 = gas.specific_heat_ratio;
+        let r: f64 = gas.gas_constant();
+        let specific_heat: f64 = specific_heat_ratio / (specific_heat_ratio - 1.0) * r;
+        let freestream_mach: f64 = freestream_velocity / (specific_heat_ratio * r * freestream_temperature).sqrt();
+        if freestream_mach <= 1.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        let freestream_pressure: f64 = freestream_density * r * freestream_temperature;
+
+        // p0 behind the shock and the total enthalpy, which the shock keeps
+        let normal_shock: NormalShock = NormalShock::from_upstream_mach(freestream_mach, specific_heat_ratio)?;
+        let stagnation_pressure: f64 = freestream_pressure * normal_shock.stagnation_pressure_ratio
+            / isentropic::calc_pressure_ratio_from_mach(freestream_mach, specific_heat_ratio)?;
+        let total_enthalpy: f64 = specific_heat * freestream_temperature + freestream_velocity.powi(2) / 2.0;
+        let stagnation_temperature: f64 = total_enthalpy / specific_heat;
+        let stagnation_density: f64 = stagnation_pressure / (r * stagnation_temperature);
+
+        // newtonian (due/dx)e = √(2 (pe - p∞) / ρe) / Rn
+        let velocity_gradient: f64 = (2.0 * (stagnation_pressure - freestream_pressure) / stagnation_density).sqrt() / nose_radius;
+
+        let fay_riddell_heat_flux: f64 = calc_fay_riddell_heat_flux(
+            stagnation_density * gas.viscosity(stagnation_temperature),
+            stagnation_pressure / (r * wall_temperature) * gas.viscosity(wall_temperature),
+            velocity_gradient,
+            total_enthalpy - specific_heat * wall_temperature,
+            total_enthalpy,
+            None,
+            lewis_number,
+            dissociation_enthalpy,
+        )?;
+
+        Ok(StagnationPointHeating {
+            stagnation_pressure,
+            stagnation_temperature,
+            stagnation_density,
+            velocity_gradient,
+            fay_riddell_heat_flux,
+            sutton_graves_heat_flux: calc_sutton_graves_heat_flux(nose_radius, freestream_velocity, freestream_density, None)?,
+        })
+    }
+
+    pub fn from_altitude(nose_radius: f64, freestream_velocity: f64, altitude: f64, wall_temperature: f64) -> Result<StagnationPointHeating, CompressibleFlowError> {
+        // standard atmosphere air
+        let atmosphere: StandardAtmosphere = StandardAtmosphere::from_altitude(altitude)?;
+        StagnationPointHeating::new(nose_radius, freestream_velocity, atmosphere.density, atmosphere.temperature, wall_temperature, gas::AIR, None, None)
+    }
+}
+
+pub fn calc_sutton_graves_heat_flux(nose_radius: f64, freestream_velocity: f64, freestream_density: f64, constant: Option<f64>) -> Result<f64, CompressibleFlowError> {
+    // q = k √(ρ∞ / Rn) V∞³, k = 1.7415e-4 for earth by default
+    if nose_radius <= 0.0 {
+        return Err(CompressibleFlowError::InvalidGeometry);
+    }
+    if freestream_density < 0.0 {
+        return Err(IsentropicFlowError::InvalidDensityRatio.into());
+    }
+    Ok(constant.unwrap_or(SUTTON_GRAVES_EARTH) * (freestream_density / nose_radius).sqrt() * freestream_velocity.abs().powi(3))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn calc_fay_riddell_heat_flux(
+    edge_density_viscosity: f64,        // ρe μe
+    wall_density_viscosity: f64,        // ρw μw
+    velocity_gradient: f64,             // (due/dx)e
+    enthalpy_difference: f64,           // h0e - hw
+    total_enthalpy: f64,                // h0e
+    prandtl_number: Option<f64>,
+    lewis_number: Option<f64>,
+    dissociation_enthalpy: Option<f64>,
+) -> Result<f64, CompressibleFlowError> {
+    // q = 0.763 Pr^-0.6 (ρw μw)^0.1 (ρe μe)^0.4 √(due/dx) (h0e - hw) (1 + (Le^0.52 - 1) hD / h0e)
+    // for an equilibrium boundary layer on a sphere
+    if edge_density_viscosity <= 0.0 || wall_density_viscosity <= 0.0 || velocity_gradient < 0.0 || total_enthalpy <= 0.0 {
+        return Err(IsentropicFlowError::MathError.into());
+    }
+    let prandtl_number: f64 = prandtl_number.unwrap_or(DEFAULT_PRANDTL_NUMBER);
+    let lewis_number: f64 = lewis_number.unwrap_or(DEFAULT_LEWIS_NUMBER);
+    let dissociation_enthalpy: f64 = dissociation_enthalpy.unwrap_or(0.0);
+    Ok(0.763 * prandtl_number.powf(-0.6)
+        * wall_density_viscosity.powf(0.1) * edge_density_viscosity.powf(0.4)
+        * velocity_gradient.sqrt() * enthalpy_difference
+        * (1.0 + (lewis_number.powf(0.52) - 1.0) * dissociation_enthalpy / total_enthalpy))
+}
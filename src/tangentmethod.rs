@@ -0,0 +1,164 @@
+use std::f64::consts::PI;
+use crate::isentropic::{self, valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::newtonian;
+use crate::shockexpansion::{calc_force_coefficients, calc_shock, split_surface, AirfoilPanel};
+use crate::taylormaccoll::SupersonicCone;
+
+
+// local surface inclination methods, every panel takes the pressure of a wedge
+// or cone with its inclination to the freestream and leeward panels expand
+// from the freestream with prandtl meyer, past detachment the panel falls back
+// to modified newtonian since neither the wedge nor the cone has an attached shock
+#[derive(Debug, Clone, Copy)]
+pub enum TangentMethod {
+    TangentWedge, // oblique shock on a wedge of the panel inclination
+    TangentCone,  // taylor maccoll cone of the panel inclination
+}
+
+#[derive(Debug)]
+pub struct TangentMethodAirfoil {
+    // same surface convention and reference lengths as ShockExpansionAirfoil
+    pub upper_panels: Vec<AirfoilPanel>,
+    pub lower_panels: Vec<AirfoilPanel>,
+    pub lift_coefficient: f64,
+    pub wave_drag_coefficient: f64,
+    pub moment_coefficient: f64,
+}
+
+#[derive(Debug)]
+pub struct TangentMethodBody {
+    // body of revolution at zero incidence, the profile is (x, r) from the nose
+    // to the base and the drag is based on the largest cross section
+    pub panels: Vec<AirfoilPanel>,
+    pub reference_area: f64,        // π rmax²
+    pub wave_drag_coefficient: f64, // forebody and afterbody pressure drag, no base drag
+}
+
+impl TangentMethodAirfoil {
+    pub fn new(surface: &[(f64, f64)], freestream_mach: f64, angle_of_attack: f64, method: TangentMethod, specific_heat_ratio: f64) -> Result<TangentMethodAirfoil, CompressibleFlowError> {
+        // surface in selig order like ShockExpansionAirfoil, compression is positive
+        if !valid_specific_heat_ratio(specific_heat_ratio) {
+            return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+        }
+        if freestream_mach <= 1.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        let (upper_surface, lower_surface) = split_surface(surface)?;
+        let panel_angle = |pair: &[(f64, f64)]| (pair[1].1 - pair[0].1).atan2(pair[1].0 - pair[0].0);
+
+        let upper_panels: Vec<AirfoilPanel> = upper_surface.windows(2)
+            .map(|pair| calc_panel(pair, panel_angle(pair) - angle_of_attack, freestream_mach, method, specific_heat_ratio))
+            .collect::<Result<_, _>>()?;
+        let lower_panels: Vec<AirfoilPanel> = lower_surface.windows(2)
+            .map(|pair| calc_panel(pair, angle_of_attack - panel_angle(pair), freestream_mach, method, specific_heat_ratio))
+            .collect::<Result<_, _>>()?;
+
+        let chord: f64 = surface[0].0 - upper_surface[0].0;
+        let reference: (f64, f64) = (upper_surface[0].0 + 0.25 * chord, upper_surface[0].1);
+        let (lift_coefficient, wave_drag_coefficient, moment_coefficient) =
+            calc_force_coefficients(&upper_panels, &lower_panels, angle_of_attack, chord, reference);
+
+        Ok(TangentMethodAirfoil {
+            upper_panels,
+            lower_panels,
+            lift_coefficient,
+            wave_drag_coefficient,
+            moment_coefficient,
+        })
+    }
+}
+
+impl TangentMethodBody {
+    pub fn new(profile: &[(f64, f64)], freestream_mach: f64, method: TangentMethod, specific_heat_ratio: f64) -> Result<TangentMethodBody, CompressibleFlowError> {
+        if !valid_specific_heat_ratio(specific_heat_ratio) {
+            return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+        }
+        if freestream_mach <= 1.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        if profile.len() < 2 || profile.iter().any(|point| point.1 < 0.0) || profile.windows(2).any(|pair| pair[1].0 <= pair[0].0) {
+            return Err(CompressibleFlowError::InvalidGeometry);
+        }
+        let max_radius: f64 = profile.iter().map(|point| point.1).fold(0.0, f64::max);
+        if max_radius <= 0.0 {
+            return Err(CompressibleFlowError::InvalidGeometry);
+        }
+
+        let panels: Vec<AirfoilPanel> = profile.windows(2)
+            .map(|pair| calc_panel(pair, (pair[1].1 - pair[0].1).atan2(pair[1].0 - pair[0].0), freestream_mach, method, specific_heat_ratio))
+            .collect::<Result<_, _>>()?;
+
+        // each frustum pushes back with Cp times its projected annulus π (r2² - r1²)
+        let reference_area: f64 = PI * max_radius.powi(2);
+        let drag: f64 = panels.iter()
+            .map(|panel| panel.pressure_coefficient * PI * (panel.end.1.powi(2) - panel.start.1.powi(2)))
+            .sum();
+
+        Ok(TangentMethodBody {
+            panels,
+            reference_area,
+            wave_drag_coefficient: drag / reference_area,
+        })
+    }
+}
+
+pub fn calc_pressure_ratio(inclination_angle: f64, freestream_mach: f64, method: TangentMethod, specific_heat_ratio: f64) -> Result<(f64, f64), CompressibleFlowError> {
+    // (M, p / p∞) on a panel inclined at the given angle to the freestream,
+    // positive into the flow
+    let (mach_number, pressure_ratio, _) = calc_panel_state(inclination_angle, freestream_mach, method, specific_heat_ratio)?;
+    Ok((mach_number, pressure_ratio))
+}
+
+fn calc_panel_state(inclination_angle: f64, freestream_mach: f64, method: TangentMethod, specific_heat_ratio: f64) -> Result<(f64, f64, bool), CompressibleFlowError> {
+    // (M, p / p∞, detached)
+    if freestream_mach <= 1.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    if inclination_angle.abs() >= PI / 2.0 {
+        return Err(CompressibleFlowError::InvalidGeometry);
+    }
+    if inclination_angle < 0.0 {
+        let prandtl_meyer_angle: f64 = isentropic::prandtl_meyer_function(freestream_mach, specific_heat_ratio)? - inclination_angle;
+        let mach_number: f64 = isentropic::calc_mach_from_prandtl_meyer_angle(prandtl_meyer_angle, specific_heat_ratio)?;
+        let pressure_ratio: f64 = isentropic::calc_pressure_ratio_from_mach(mach_number, specific_heat_ratio)?
+            / isentropic::calc_pressure_ratio_from_mach(freestream_mach, specific_heat_ratio)?;
+        return Ok((mach_number, pressure_ratio, false));
+    }
+    if inclination_angle == 0.0 {
+        return Ok((freestream_mach, 1.0, false));
+    }
+
+    let attached: Option<(f64, f64)> = match method {
+        TangentMethod::TangentWedge => calc_shock(freestream_mach, inclination_angle, specific_heat_ratio)?
+            .map(|shock| (shock.downstream_mach, shock.pressure_ratio)),
+        TangentMethod::TangentCone => match SupersonicCone::from_mach_and_cone_angle(freestream_mach, inclination_angle, specific_heat_ratio) {
+            Ok(cone) => Some((cone.surface_mach, cone.surface_pressure_ratio)),
+            Err(CompressibleFlowError::DetachedShock) => None,
+            Err(e) => return Err(e),
+        },
+    };
+    match attached {
+        Some((mach_number, pressure_ratio)) => Ok((mach_number, pressure_ratio, false)),
+        None => {
+            // detached, modified newtonian with the mach number from the pitot pressure
+            let pressure_coefficient: f64 = newtonian::calc_max_pressure_coefficient(freestream_mach, specific_heat_ratio)? * inclination_angle.sin().powi(2);
+            let pressure_ratio: f64 = 1.0 + specific_heat_ratio * freestream_mach.powi(2) / 2.0 * pressure_coefficient;
+            let pitot_pressure_ratio: f64 = 1.0 + specific_heat_ratio * freestream_mach.powi(2) / 2.0 * newtonian::calc_max_pressure_coefficient(freestream_mach, specific_heat_ratio)?;
+            let mach_number: f64 = isentropic::calc_mach_from_pressure_ratio((pressure_ratio / pitot_pressure_ratio).min(1.0), specific_heat_ratio)?;
+            Ok((mach_number, pressure_ratio, true))
+        }
+    }
+}
+
+fn calc_panel(pair: &[(f64, f64)], inclination_angle: f64, freestream_mach: f64, method: TangentMethod, specific_heat_ratio: f64) -> Result<AirfoilPanel, CompressibleFlowError> {
+    let (mach_number, pressure_ratio, detached) = calc_panel_state(inclination_angle, freestream_mach, method, specific_heat_ratio)?;
+    Ok(AirfoilPanel {
+        start: pair[0],
+        end: pair[1],
+        mach_number: Some(mach_number),
+        pressure_ratio,
+        pressure_coefficient: 2.0 / (specific_heat_ratio * freestream_mach.powi(2)) * (pressure_ratio - 1.0),
+        detached,
+    })
+}
@@ -1,7 +1,12 @@
 #![allow(dead_code)]
 
+use std::f64::consts::PI;
 use crate::isentropic::{self, valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::normalshock;
 use crate::obliqueshock; 
+use crate::numerics::{bisection, golden_section_maximum};
+use crate::gas::GasProperties;
 
 pub enum Input {
     UpstreamMach(f64),
@@ -23,28 +28,90 @@ pub enum Output {
     SurfaceDensityRatio, 
     SurfaceTemperatureRatio,        
     SurfaceStagnationPressureratio, 
+    EntropyChange,
+    StagnationTemperatureRatio,
+    VelocityRatio,
+    InternalEnergyChange,
+    EnthalpyChange,
 }
 
 #[derive(Debug)]
 pub struct SupersonicCone {
-    upstream_mach: f64,                     // M1
-    shock_angle: f64,                       // β
-    surface_mach: f64,               // Mc (mach number at the surface of the cone)
-    cone_angle: f64,                        // σ
-    shock_turn_angle: f64,                  // δ
-    pressure_ratio: f64,                    // p2 / p1
-    density_ratio: f64,                     // ρ2 / ρ1
-    temperature_ratio: f64,                 // T2 / T1
-    stagnation_pressure_ratio: f64,         // p02 / p01
-    surface_pressure_ratio: f64,            // pc / p1
-    surface_density_ratio: f64,             // ρc / ρ1
-    surface_temperature_ratio: f64,         // Tc / T1
-    surface_stagnation_pressure_ratio: f64, // p0c / p01 (will equal p02 / p01 since perfectly isentropic compression)
+    pub(crate) upstream_mach: f64,                     // M1
+    pub(crate) shock_angle: f64,                       // β
+    pub(crate) surface_mach: f64,                      // Mc (mach number at the surface of the cone)
+    pub(crate) cone_angle: f64,                        // σ
+    pub(crate) shock_turn_angle: f64,                  // δ
+    pub(crate) pressure_ratio: f64,                    // p2 / p1
+    pub(crate) density_ratio: f64,                     // ρ2 / ρ1
+    pub(crate) temperature_ratio: f64,                 // T2 / T1
+    pub(crate) stagnation_pressure_ratio: f64,         // p02 / p01
+    pub(crate) surface_pressure_ratio: f64,            // pc / p1
+    pub(crate) surface_density_ratio: f64,             // ρc / ρ1
+    pub(crate) surface_temperature_ratio: f64,         // Tc / T1
+    pub(crate) surface_stagnation_pressure_ratio: f64, // p0c / p01 (will equal p02 / p01 since perfectly isentropic compression)
+    pub(crate) entropy_change: f64,                    // (s2 - s1) / R across the shock, unchanged up to the surface
+    pub(crate) stagnation_temperature_ratio: f64,      // T02 / T01
+    pub(crate) velocity_ratio: f64,                    // V2 / V1 just behind the shock
+    pub(crate) internal_energy_change: f64,            // (e2 - e1) / (p1 v1) across the shock
+    pub(crate) enthalpy_change: f64,                   // (h2 - h1) / (p1 v1) across the shock
 }
 
 impl SupersonicCone {
-    pub fn from_mach_and_cone_angle() {
-        // todo
+    pub fn from_mach_and_cone_angle(upstream_mach: f64, cone_angle: f64, specific_heat_ratio: f64) -> Result<SupersonicCone, CompressibleFlowError> {
+        // the attached weak shock, the cone angle grows with the shock angle from
+        // the mach angle up to the detachment angle, so step out until the cone
+        // is passed and bisect back, DetachedShock once the cone is past the
+        // largest one with an attached shock
+        if !valid_specific_heat_ratio(specific_heat_ratio) {
+            return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+        }
+        if upstream_mach <= 1.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        if cone_angle <= 0.0 || cone_angle >= PI / 2.0 {
+            return Err(CompressibleFlowError::InvalidGeometry);
+        }
+        let calc_cone_angle = |shock_angle: f64| match SupersonicCone::from_mach_and_shock_angle(upstream_mach, shock_angle, specific_heat_ratio) {
+            Ok(cone) => cone.cone_angle,
+            Err(_) => f64::NAN,
+        };
+
+        let step: f64 = 0.5_f64.to_radians();
+        let mut lower_bound: f64 = (1.0 / upstream_mach).asin().max(cone_angle) + 1e-6;
+        // a failure this close to the mach wave is not detachment, so pass it on
+        let mut lower_cone_angle: f64 = SupersonicCone::from_mach_and_shock_angle(upstream_mach, lower_bound, specific_heat_ratio)?.cone_angle;
+        let mut previous_bound: f64 = lower_bound;
+        let upper_bound: f64 = loop {
+            let upper_bound: f64 = (lower_bound + step).min(PI / 2.0);
+            let upper_cone_angle: f64 = calc_cone_angle(upper_bound);
+            if upper_cone_angle >= cone_angle {
+                break upper_bound;
+            }
+            // past the largest cone angle, or behind a subsonic shock, the
+            // largest one lies between the last two steps and may still be
+            // larger than the cone
+            if upper_cone_angle.is_nan() || upper_cone_angle < lower_cone_angle || upper_bound >= PI / 2.0 {
+                let max_shock_angle: f64 = golden_section_maximum(&calc_cone_angle, previous_bound, upper_bound, Some(1e-10), None);
+                if calc_cone_angle(max_shock_angle) >= cone_angle {
+                    // the weak root is ahead of the maximum
+                    lower_bound = previous_bound;
+                    break max_shock_angle;
+                }
+                return Err(CompressibleFlowError::DetachedShock);
+            }
+            previous_bound = lower_bound;
+            lower_bound = upper_bound;
+            lower_cone_angle = upper_cone_angle;
+        };
+
+        let f = |shock_angle: f64| calc_cone_angle(shock_angle) - cone_angle;
+        let (f_lower, f_upper) = (f(lower_bound), f(upper_bound));
+        if !f_lower.is_finite() || !f_upper.is_finite() {
+            return Err(IsentropicFlowError::MathError.into());
+        }
+        let shock_angle: f64 = bisection(&f, lower_bound, upper_bound, None, None);
+        Ok(SupersonicCone::from_mach_and_shock_angle(upstream_mach, shock_angle, specific_heat_ratio)?)
     }
 
     pub fn from_mach_and_shock_angle(upstream_mach: f64, shock_angle: f64, specific_heat_ratio: f64) -> Result<SupersonicCone, IsentropicFlowError> {
@@ -54,11 +121,19 @@ impl SupersonicCone {
         let temperature_ratio: f64 = obliqueshock::calc_temperature_ratio(upstream_mach, shock_angle, specific_heat_ratio)?;
         let stagnation_pressure_ratio: f64 = obliqueshock::calc_stagnation_pressure_ratio(upstream_mach, shock_angle, specific_heat_ratio)?;
         let deflection_angle: f64 = obliqueshock::calc_deflection_angle(upstream_mach, shock_angle, specific_heat_ratio)?;
+        let normal_upstream_mach: f64 = obliqueshock::calc_normal_upstream_mach(upstream_mach, shock_angle)?;
+        let entropy_change: f64 = normalshock::calc_entropy_change_from_upstream_mach(normal_upstream_mach, specific_heat_ratio)?;
+        let stagnation_temperature_ratio: f64 = normalshock::calc_stagnation_temperature_ratio_from_upstream_mach(normal_upstream_mach, specific_heat_ratio)?;
+        let velocity_ratio: f64 = obliqueshock::calc_velocity_ratio(upstream_mach, shock_angle, specific_heat_ratio)?;
+        let internal_energy_change: f64 = normalshock::calc_internal_energy_change_from_upstream_mach(normal_upstream_mach, specific_heat_ratio)?;
+        let enthalpy_change: f64 = normalshock::calc_enthalpy_change_from_upstream_mach(normal_upstream_mach, specific_heat_ratio)?;
         
-        // get downstream velocity components for solving taylor maccoll
+        // get downstream velocity components for solving taylor maccoll, scaled
+        // by the maximum velocity V' = (2 / ((γ - 1) M²) + 1)^-1/2 as the equations expect
         let downstream_mach = obliqueshock::calc_downstream_mach_from_shock_angle(upstream_mach, shock_angle, specific_heat_ratio)?;
-        let radial_downstream_mach = downstream_mach * (shock_angle - deflection_angle).cos();
-        let tangential_downstream_mach = - downstream_mach *(shock_angle - deflection_angle).sin();
+        let downstream_velocity = (2.0 / ((specific_heat_ratio - 1.0) * downstream_mach.powi(2)) + 1.0).powf(-0.5);
+        let radial_downstream_mach = downstream_velocity * (shock_angle - deflection_angle).cos();
+        let tangential_downstream_mach = - downstream_velocity * (shock_angle - deflection_angle).sin();
     
         // solve taylor maccoll eqautions
         let (velocity_components, thetas) = 
@@ -72,10 +147,11 @@ impl SupersonicCone {
             )?;
 
         // extrapolate cone angle and surface mach number from results
-        let cone_angle: f64 = *thetas.last().ok_or_else(|| IsentropicFlowError::WhatTheFuck)?;
-        let surface_mach: f64 = velocity_components.last()
+        let cone_angle: f64 = *thetas.last().ok_or(IsentropicFlowError::WhatTheFuck)?;
+        let surface_velocity: f64 = velocity_components.last()
             .map(|&(radial, _)| radial)
-            .ok_or_else(||IsentropicFlowError::WhatTheFuck)?;
+            .ok_or(IsentropicFlowError::WhatTheFuck)?;
+        let surface_mach: f64 = (2.0 / (specific_heat_ratio - 1.0) * surface_velocity.powi(2) / (1.0 - surface_velocity.powi(2))).sqrt();
         let shock_turn_angle: f64 = shock_angle - cone_angle;
 
         // and get the other values (probably make these into their own functions)
@@ -85,14 +161,13 @@ impl SupersonicCone {
             isentropic::calc_pressure_ratio_from_mach(surface_mach, specific_heat_ratio)?;              // pc / p0c = pc / p02
 
         let surface_density_ratio: f64 = 
-            obliqueshock::calc_density_ratio(upstream_mach, shock_angle, specific_heat_ratio)? *            // rho2 / rho1
-            (1.0 / isentropic::calc_density_ratio_from_mach(surface_mach, specific_heat_ratio)?) *  // rho0c / rhoc
-            isentropic::calc_density_ratio_from_mach(upstream_mach, specific_heat_ratio)?;          // rho1 / rho01
+            obliqueshock::calc_stagnation_pressure_ratio(upstream_mach, shock_angle, specific_heat_ratio)? *    // ρ0c / ρ01 = p02 / p01
+            (1.0 / isentropic::calc_density_ratio_from_mach(upstream_mach, specific_heat_ratio)?) *         // ρ01 / ρ1
+            isentropic::calc_density_ratio_from_mach(surface_mach, specific_heat_ratio)?;                   // ρc / ρ0c
 
         let surface_temperature_ratio: f64 = 
-            obliqueshock::calc_temperature_ratio(upstream_mach, shock_angle, specific_heat_ratio)? *
-            (1.0 / isentropic::calc_temperature_ratio_from_mach(surface_mach, specific_heat_ratio)?) *  
-            isentropic::calc_mach_from_temperature_ratio(upstream_mach, specific_heat_ratio)?;          
+            (1.0 / isentropic::calc_temperature_ratio_from_mach(upstream_mach, specific_heat_ratio)?) *     // T01 / T1 = T0c / T1
+            isentropic::calc_temperature_ratio_from_mach(surface_mach, specific_heat_ratio)?;               // Tc / T0c
 
         Ok(SupersonicCone{
             upstream_mach, 
@@ -108,6 +183,11 @@ impl SupersonicCone {
             surface_density_ratio,
             surface_temperature_ratio,
             surface_stagnation_pressure_ratio: stagnation_pressure_ratio,
+            entropy_change,
+            stagnation_temperature_ratio,
+            velocity_ratio,
+            internal_energy_change,
+            enthalpy_change,
         })
     }
 
@@ -116,6 +196,50 @@ impl SupersonicCone {
     }
 }
 
+pub fn calculate(input: Vec<Input>, output: Output, specific_heat_ratio: f64) -> Result<f64, CompressibleFlowError> {
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+
+    let supersonic_cone = match input.as_slice() {
+        [Input::UpstreamMach(upstream_mach), Input::ShockAngle(shock_angle)] => {
+            SupersonicCone::from_mach_and_shock_angle(*upstream_mach, *shock_angle, specific_heat_ratio)?
+        }
+        [Input::UpstreamMach(upstream_mach), Input::ConeAngle(cone_angle)] => {
+            SupersonicCone::from_mach_and_cone_angle(*upstream_mach, *cone_angle, specific_heat_ratio)?
+        }
+        _ => {
+            return Err(IsentropicFlowError::WhatTheFuck.into());
+        }
+    };
+
+    match output {
+        Output::SurfaceMachNumber => Ok(supersonic_cone.surface_mach),
+        Output::ConeAngle => Ok(supersonic_cone.cone_angle),
+        Output::ShockAngle => Ok(supersonic_cone.shock_angle),
+        Output::ShockTurnAngle => Ok(supersonic_cone.shock_turn_angle),
+        Output::PressureRatio => Ok(supersonic_cone.pressure_ratio),
+        Output::DensityRatio => Ok(supersonic_cone.density_ratio),
+        Output::TemperatureRatio => Ok(supersonic_cone.temperature_ratio),
+        Output::StagnationPressureRatio => Ok(supersonic_cone.stagnation_pressure_ratio),
+        Output::SurfacePressureRatio => Ok(supersonic_cone.surface_pressure_ratio),
+        Output::SurfaceDensityRatio => Ok(supersonic_cone.surface_density_ratio),
+        Output::SurfaceTemperatureRatio => Ok(supersonic_cone.surface_temperature_ratio),
+        Output::SurfaceStagnationPressureratio => Ok(supersonic_cone.surface_stagnation_pressure_ratio),
+        Output::EntropyChange => Ok(supersonic_cone.entropy_change),
+        Output::StagnationTemperatureRatio => Ok(supersonic_cone.stagnation_temperature_ratio),
+        Output::VelocityRatio => Ok(supersonic_cone.velocity_ratio),
+        Output::InternalEnergyChange => Ok(supersonic_cone.internal_energy_change),
+        Output::EnthalpyChange => Ok(supersonic_cone.enthalpy_change),
+    }
+}
+
+pub fn calculate_for_gas(input: Vec<Input>, output: Output, gas: GasProperties) -> Result<f64, CompressibleFlowError> {
+    // same as calculate with γ taken from a catalogue gas
+    calculate(input, output, gas.specific_heat_ratio)
+}
+
+#[allow(clippy::type_complexity)]
 pub fn solve_taylor_maccoll(
     initial_velocity_vector: (f64, f64),
     initial_angle: f64, // typically the shock angle created by the cone
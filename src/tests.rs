@@ -1,10 +1,35 @@
 #![cfg(test)]
 
 use std::f64::consts::PI;
-use crate::isentropic::IsentropicFlow;
-use crate::normalshock::NormalShock;
+use crate::isentropic::{self, IsentropicFlow, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::normalshock::{self, NormalShock};
 use crate::obliqueshock::{self, ObliqueShock};
 use crate::taylormaccoll;
+use crate::nozzleflow;
+use crate::characteristics::{self, MinimumLengthNozzle};
+use crate::bellnozzle::{BellContour, BellNozzle};
+use crate::shockexpansion::ShockExpansionAirfoil;
+use crate::thinairfoil::{self, ThinAirfoil, ThinAirfoilTheory};
+use crate::compressibility::{self, CompressibilityCorrection};
+use crate::pitot;
+use crate::airdata::{self, AirData, StandardAtmosphere};
+use crate::hugoniot;
+use crate::detonation::{self, DetonationBranch};
+use crate::znd::ZndDetonation;
+use crate::gas::{self, GasModel, HeatCapacity, Species, NITROGEN, OXYGEN};
+use crate::mixture::Mixture;
+use crate::liquid::{self, LiquidModel, LiquidShock, LiquidState, RiemannWave, WATER_STIFFENED_GAS, WATER_TAIT};
+use crate::realgas::{CubicEquationOfState, CubicGas, RealGasShock};
+use crate::boundarylayer::{self, BoundaryLayerRegime, EdgeConditions, FlatPlateBoundaryLayer, SkinFrictionMethod};
+use crate::similarity::{SimilarityBoundaryLayer, ViscosityLaw};
+use crate::dissociation::EquilibriumNormalShock;
+use crate::relaxation::VibrationalRelaxation;
+use crate::stagnationheating::{self, StagnationPointHeating};
+use crate::bluntbody::{self, BilligShock, BluntBody};
+use crate::newtonian::{self, NewtonianAerodynamics, NewtonianMethod, ReferenceGeometry, TriangleMesh};
+use crate::tangentmethod::{self, TangentMethod, TangentMethodAirfoil, TangentMethodBody};
+use crate::numerics;
 
 #[test]
 fn test_isentropic() {
@@ -22,6 +47,29 @@ fn test_isentropic() {
     }
 }
 
+#[test]
+fn test_calc_mach_from_area_ratio() {
+    // both branches invert the area-mach relation, an area ratio past the
+    // bracket of either branch is an error rather than the bracket end
+    let specific_heat_ratio = 1.4;
+
+    for (mach_number, supersonic) in [(0.3, false), (2.0, true), (50.0, true)] {
+        let area_ratio = isentropic::calc_area_ratio_from_mach(mach_number, specific_heat_ratio).unwrap();
+        match isentropic::calc_mach_from_area_ratio(area_ratio, specific_heat_ratio, supersonic) {
+            Ok(inverse) => {
+                assert!((inverse - mach_number).abs() / mach_number < 1e-9);
+            }
+            Err(e) => {
+                panic!("area-mach inverse failed: {:?}", e);
+            }
+        }
+    }
+
+    let area_ratio = isentropic::calc_area_ratio_from_mach(150.0, specific_heat_ratio).unwrap();
+    assert!(matches!(isentropic::calc_mach_from_area_ratio(area_ratio, specific_heat_ratio, true), Err(IsentropicFlowError::InvalidAreaRatio)));
+    assert!(matches!(isentropic::calc_mach_from_area_ratio(1e7, specific_heat_ratio, false), Err(IsentropicFlowError::InvalidAreaRatio)));
+}
+
 #[test]
 fn test_normal_shock() {
     // working
@@ -38,6 +86,28 @@ fn test_normal_shock() {
     }
 }
 
+#[test]
+fn test_normal_shock_temperature_ratio_inverse() {
+    // T2 / T1 = 1.6875 behind a mach 2 normal shock, the inverse has to give
+    // mach 2 back across the whole range
+    let specific_heat_ratio = 1.4;
+
+    match normalshock::calc_upstream_mach_from_temperature_ratio(1.6875, specific_heat_ratio) {
+        Ok(upstream_mach) => {
+            assert!((upstream_mach - 2.0).abs() < 1e-9);
+        }
+        Err(e) => {
+            panic!("temperature ratio inverse failed: {:?}", e);
+        }
+    }
+
+    for upstream_mach in [1.2, 3.0, 8.0] {
+        let temperature_ratio = normalshock::calc_temperature_ratio_from_upstream_mach(upstream_mach, specific_heat_ratio).expect("temperature ratio failed");
+        let recovered_mach = normalshock::calc_upstream_mach_from_temperature_ratio(temperature_ratio, specific_heat_ratio).expect("temperature ratio inverse failed");
+        assert!((recovered_mach - upstream_mach).abs() < 1e-9);
+    }
+}
+
 #[test]
 fn test_calc_shock_angle_from_deflection_angle() {
     // working
@@ -81,6 +151,24 @@ fn test_oblique_shock() {
     }
 }
 
+#[test]
+fn test_calc_max_shock_angle() {
+    // βmax = 64.67 deg at mach 2, where the deflection peaks at 22.97 deg
+    let specific_heat_ratio = 1.4;
+
+    match obliqueshock::calc_max_shock_angle(2.0, specific_heat_ratio) {
+        Ok(max_shock_angle) => {
+            let max_deflection_angle = obliqueshock::calc_deflection_angle(2.0, max_shock_angle, specific_heat_ratio).expect("deflection failed");
+            println!("βmax {:.4} deg, θmax {:.4} deg", max_shock_angle.to_degrees(), max_deflection_angle.to_degrees());
+            assert!((max_shock_angle.to_degrees() - 64.67).abs() < 0.01);
+            assert!((max_deflection_angle.to_degrees() - 22.97).abs() < 0.01);
+        }
+        Err(e) => {
+            panic!("max shock angle failed: {:?}", e);
+        }
+    }
+}
+
 #[test]
 fn test_solve_taylor_maccoll() {
     // yeah it doesnt quite work
@@ -143,4 +231,1092 @@ fn test_taylor_maccoll() {
             panic!("why, {:?}", e)
         }
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_taylor_maccoll_cone_tables() {
+    // mach 4 with a 30 degree shock sits on a 23.5 degree cone, pc / p1 = 5.01
+    // and Mc = 2.66 from the cone tables
+    match taylormaccoll::SupersonicCone::from_mach_and_shock_angle(4.0, PI / 6.0, 1.4) {
+        Ok(cone) => {
+            assert!((cone.cone_angle.to_degrees() - 23.49).abs() < 0.05);
+            assert!((cone.surface_pressure_ratio - 5.01).abs() < 0.01);
+            assert!((cone.surface_mach - 2.66).abs() < 0.01);
+            // the surface state is a perfect gas state, p = ρ T
+            assert!((cone.surface_density_ratio * cone.surface_temperature_ratio - cone.surface_pressure_ratio).abs() < 1e-9);
+        }
+        Err(e) => {
+            panic!("cone failed: {:?}", e);
+        }
+    }
+}
+
+#[test]
+fn test_nozzle_flow_isentropic() {
+    // anderson's convergent-divergent nozzle, should match the area-mach relation
+    let area = |x: f64| 1.0 + 2.2 * (x - 1.5).powi(2);
+    let specific_heat_ratio = 1.4;
+
+    match nozzleflow::solve_nozzle_flow(&area, 0.0, 3.0, 61, nozzleflow::OutletCondition::Supersonic, specific_heat_ratio, None, None, None, None) {
+        Ok(nozzle_flow) => {
+            assert!(nozzle_flow.converged);
+            let exact_mach = nozzle_flow.isentropic_mach_number(specific_heat_ratio).expect("area-mach failed");
+            for (computed, exact) in nozzle_flow.mach_number.iter().zip(exact_mach.iter()) {
+                assert!((computed - exact).abs() / exact < 0.03, "computed {} exact {}", computed, exact);
+            }
+        }
+        Err(e) => {
+            panic!("nozzle failed: {:?}", e);
+        }
+    }
+}
+
+#[test]
+fn test_nozzle_flow_normal_shock() {
+    let area = |x: f64| 1.0 + 2.2 * (x - 1.5).powi(2);
+    let specific_heat_ratio = 1.4;
+    let back_pressure_ratio = 0.6784;
+
+    let (shock_area_ratio, _) = nozzleflow::calc_shock_area_ratio(area(3.0), back_pressure_ratio, specific_heat_ratio).expect("no shock");
+    for invalid_ratio in [0.0, -0.5, 1.0, 1.5] {
+        assert!(matches!(nozzleflow::calc_shock_area_ratio(area(3.0), invalid_ratio, specific_heat_ratio), Err(CompressibleFlowError::Flow(IsentropicFlowError::InvalidPressureRatio))));
+    }
+    let exact_position = 1.5 + ((shock_area_ratio - 1.0) / 2.2).sqrt();
+
+    match nozzleflow::solve_nozzle_flow(&area, 0.0, 3.0, 61, nozzleflow::OutletCondition::BackPressureRatio(back_pressure_ratio), specific_heat_ratio, None, None, None, None) {
+        Ok(nozzle_flow) => {
+            let shock_position = nozzle_flow.shock_position().expect("no shock captured");
+            println!("shock at {:.4}, exact {:.4}, {} iterations", shock_position, exact_position, nozzle_flow.iterations);
+            assert!((shock_position - exact_position).abs() < 0.1);
+        }
+        Err(e) => {
+            panic!("nozzle failed: {:?}", e);
+        }
+    }
+}
+
+#[test]
+fn test_minimum_length_nozzle() {
+    // exit height should recover the isentropic area ratio as the net is refined
+    let exit_mach = 2.4;
+    let specific_heat_ratio = 1.4;
+
+    match MinimumLengthNozzle::from_exit_mach(exit_mach, 50, specific_heat_ratio) {
+        Ok(nozzle) => {
+            let exact_area_ratio = isentropic::calc_area_ratio_from_mach(exit_mach, specific_heat_ratio).expect("area-mach failed");
+            println!("area ratio {:.4}, exact {:.4}, length {:.4}", nozzle.area_ratio, exact_area_ratio, nozzle.length);
+            assert!((nozzle.area_ratio - exact_area_ratio).abs() / exact_area_ratio < 0.01);
+            assert_eq!(nozzle.characteristic_net.len(), 50 * 51 / 2 + 50);
+            assert!(nozzle.wall_contour.windows(2).all(|pair| pair[1].0 > pair[0].0));
+        }
+        Err(e) => {
+            panic!("moc failed: {:?}", e);
+        }
+    }
+}
+
+#[test]
+fn test_march_characteristics() {
+    // isentropic compression on a concave circular arc of radius R, the simple
+    // wave first folds at a distance R sinμ / (1 + dμ/dθ) along the leading mach line
+    let specific_heat_ratio = 1.4;
+    let radius = 10.0;
+    let turning_angle = 10.0_f64.to_radians();
+    let mut lower_wall = vec![(-1.0, 0.0), (0.0, 0.0)];
+    lower_wall.extend((1..=400).map(|i| {
+        let theta = turning_angle * i as f64 / 400.0;
+        (radius * theta.sin(), radius * (1.0 - theta.cos()))
+    }));
+    lower_wall.push((radius * turning_angle.sin() + 20.0, radius * (1.0 - turning_angle.cos()) + 20.0 * turning_angle.tan()));
+    let upper_wall = [(-1.0, 4.0), (30.0, 4.0)];
+
+    let start_line = characteristics::calc_uniform_start_line(-0.5, 0.0, 4.0, 2.0, 0.0, 81, specific_heat_ratio).expect("start line failed");
+    match characteristics::march_characteristics(&start_line, &upper_wall, Some(&lower_wall), specific_heat_ratio, None) {
+        Ok(net) => {
+            let (x, y) = net.shock_formation.expect("no coalescence detected");
+            let mach_angle = 0.5_f64.asin();
+            let distance = radius * mach_angle.sin() / (1.0 + 1.8 / 3.0);
+            println!("fold at ({:.4}, {:.4}), exact ({:.4}, {:.4})", x, y, distance * mach_angle.cos(), distance * mach_angle.sin());
+            assert!((x - distance * mach_angle.cos()).abs() < 0.1);
+            assert!((y - distance * mach_angle.sin()).abs() < 0.1);
+        }
+        Err(e) => {
+            panic!("moc failed: {:?}", e);
+        }
+    }
+}
+
+#[test]
+fn test_bell_nozzle() {
+    // a full length truncated ideal contour should deliver a uniform exit at the design mach number
+    let exit_mach = 3.0;
+    let specific_heat_ratio = 1.4;
+
+    match BellNozzle::new(exit_mach, BellContour::TruncatedIdeal(1.0), 12, specific_heat_ratio) {
+        Ok(nozzle) => {
+            let exact_area_ratio = isentropic::calc_area_ratio_from_mach(exit_mach, specific_heat_ratio).expect("area-mach failed");
+            println!("area ratio {:.4}, exact {:.4}, efficiency {:.5}", nozzle.area_ratio, exact_area_ratio, nozzle.divergence_efficiency);
+            assert!((nozzle.area_ratio - exact_area_ratio).abs() / exact_area_ratio < 0.03);
+            assert!(nozzle.divergence_efficiency > 0.999);
+            assert!(nozzle.exit_mach_profile().iter().all(|&(_, mach_number)| (mach_number - exit_mach).abs() / exit_mach < 0.05));
+        }
+        Err(e) => {
+            panic!("bell nozzle failed: {:?}", e);
+        }
+    }
+}
+
+#[test]
+fn test_optimum_truncated_bell_nozzle() {
+    // an optimum truncated ideal contour keeps the ideal area ratio in a shorter length with a small divergence loss
+    let specific_heat_ratio = 1.4;
+    let ideal = BellNozzle::new(3.0, BellContour::TruncatedIdeal(1.0), 12, specific_heat_ratio).expect("ideal contour failed");
+
+    match BellNozzle::new(3.0, BellContour::OptimumTruncatedIdeal(0.7), 12, specific_heat_ratio) {
+        Ok(nozzle) => {
+            println!("length {:.4}, area ratio {:.4}, loss {:.5}", nozzle.length, nozzle.area_ratio, nozzle.divergence_loss);
+            assert!((nozzle.length - 0.7 * ideal.length).abs() / ideal.length < 0.01);
+            assert!((nozzle.area_ratio - ideal.area_ratio).abs() / ideal.area_ratio < 0.01);
+            assert!(nozzle.divergence_loss > ideal.divergence_loss);
+            assert!(nozzle.exit_wall_angle > 0.0);
+        }
+        Err(e) => {
+            panic!("bell nozzle failed: {:?}", e);
+        }
+    }
+
+    // both truncations take the same fractions, longer than the ideal contour
+    // is invalid and a length the search cannot reach is an error, not a panic
+    for contour in [BellContour::TruncatedIdeal(1.2), BellContour::OptimumTruncatedIdeal(1.2)] {
+        assert!(matches!(BellNozzle::new(3.0, contour, 12, specific_heat_ratio), Err(CompressibleFlowError::InvalidGeometry)));
+    }
+    assert!(BellNozzle::new(3.0, BellContour::OptimumTruncatedIdeal(0.05), 12, specific_heat_ratio).is_err());
+
+    // at mach 5 the planar bound on the fan angle is past where the reflected fan fails
+    assert!(BellNozzle::new(5.0, BellContour::TruncatedIdeal(1.0), 12, specific_heat_ratio).is_ok());
+}
+
+#[test]
+fn test_thrust_optimised_parabola_bell_nozzle() {
+    // an 80% bell at the mach 3 area ratio of 4.23 with rao's θn and θe, it
+    // keeps the area ratio, reaches the lip at θe and loses less to divergence
+    // than the 15° cone with λ = (1 + cos 15°) / 2
+    let specific_heat_ratio = 1.4;
+    let contour = BellContour::ThrustOptimisedParabola {
+        length_fraction: 0.8,
+        initial_wall_angle: 21.0_f64.to_radians(),
+        exit_wall_angle: 14.0_f64.to_radians(),
+    };
+
+    match BellNozzle::new(3.0, contour, 12, specific_heat_ratio) {
+        Ok(nozzle) => {
+            let area_ratio = isentropic::calc_area_ratio_from_mach(3.0, specific_heat_ratio).unwrap();
+            let cone_angle = 15.0_f64.to_radians();
+            let cone_length = (area_ratio.sqrt() - 1.0 + 1.5 * (1.0 / cone_angle.cos() - 1.0)) / cone_angle.tan();
+            println!("length {:.4}, area ratio {:.4}, θe {:.2}, loss {:.5}", nozzle.length, nozzle.area_ratio, nozzle.exit_wall_angle.to_degrees(), nozzle.divergence_loss);
+            assert!((nozzle.length - 0.8 * cone_length).abs() < 1e-9);
+            assert!((nozzle.area_ratio - area_ratio).abs() / area_ratio < 1e-9);
+            assert!((nozzle.exit_wall_angle - 14.0_f64.to_radians()).abs() < 0.5_f64.to_radians());
+            assert!(nozzle.divergence_loss < 1.0 - (1.0 + cone_angle.cos()) / 2.0);
+        }
+        Err(e) => {
+            panic!("bell nozzle failed: {:?}", e);
+        }
+    }
+
+    // tangents that meet past the lip cannot be joined by the parabola
+    let contour = BellContour::ThrustOptimisedParabola {
+        length_fraction: 0.8,
+        initial_wall_angle: 10.0_f64.to_radians(),
+        exit_wall_angle: 5.0_f64.to_radians(),
+    };
+    assert!(matches!(BellNozzle::new(3.0, contour, 12, specific_heat_ratio), Err(CompressibleFlowError::InvalidGeometry)));
+}
+
+#[test]
+fn test_shock_expansion_airfoil() {
+    // a flat plate has cd / cl = tan α exactly, a thin diamond lands close to
+    // the linear theory drag 4 (α² + (t/c)²) / √(M² - 1)
+    let specific_heat_ratio = 1.4;
+    let angle_of_attack = 5.0_f64.to_radians();
+    let flat_plate = [(1.0, 0.0), (0.0, 0.0), (1.0, 0.0)];
+    let diamond = [(1.0, 0.0), (0.5, 0.05), (0.0, 0.0), (0.5, -0.05), (1.0, 0.0)];
+
+    match ShockExpansionAirfoil::new(&flat_plate, 2.0, angle_of_attack, specific_heat_ratio) {
+        Ok(airfoil) => {
+            println!("flat plate cl {:.5}, cd {:.5}", airfoil.lift_coefficient, airfoil.wave_drag_coefficient);
+            assert!((airfoil.wave_drag_coefficient / airfoil.lift_coefficient - angle_of_attack.tan()).abs() < 1e-9);
+            assert!(airfoil.detached_corner.is_none());
+        }
+        Err(e) => {
+            panic!("shock expansion failed: {:?}", e);
+        }
+    }
+
+    match ShockExpansionAirfoil::new(&diamond, 2.0, 0.0, specific_heat_ratio) {
+        Ok(airfoil) => {
+            let linear_drag = 4.0 * 0.1_f64.powi(2) / 3.0_f64.sqrt();
+            println!("diamond cd {:.5}, linear {:.5}", airfoil.wave_drag_coefficient, linear_drag);
+            assert!(airfoil.lift_coefficient.abs() < 1e-12);
+            assert!((airfoil.wave_drag_coefficient - linear_drag).abs() / linear_drag < 0.02);
+        }
+        Err(e) => {
+            panic!("shock expansion failed: {:?}", e);
+        }
+    }
+
+    // 5.7 deg of wedge is past the maximum deflection at mach 1.2, both surfaces
+    // fall back to modified newtonian from the leading edge and still integrate
+    let airfoil = ShockExpansionAirfoil::new(&diamond, 1.2, 0.0, specific_heat_ratio).expect("shock expansion failed");
+    assert_eq!(airfoil.detached_corner, Some((0.0, 0.0)));
+    assert!(airfoil.upper_panels.iter().chain(airfoil.lower_panels.iter()).all(|panel| panel.detached && panel.mach_number.is_none()));
+    assert!(airfoil.upper_panels[1].pressure_coefficient == 0.0);
+    assert!(airfoil.lift_coefficient.abs() < 1e-12 && airfoil.wave_drag_coefficient > 0.0 && airfoil.wave_drag_coefficient.is_finite());
+}
+
+#[test]
+fn test_thin_airfoil() {
+    // ackeret integrates to cl = 4α / β and cd = 4 (α² + (t/c)²) / β on a
+    // diamond, busemann pressures should sit much closer to shock expansion
+    let specific_heat_ratio = 1.4;
+    let angle_of_attack = 2.0_f64.to_radians();
+    let diamond = [(1.0, 0.0), (0.5, 0.05), (0.0, 0.0), (0.5, -0.05), (1.0, 0.0)];
+    let beta = 3.0_f64.sqrt();
+
+    match ThinAirfoil::new(&diamond, 2.0, angle_of_attack, ThinAirfoilTheory::Ackeret, specific_heat_ratio) {
+        Ok(airfoil) => {
+            println!("cl {:.5}, cd {:.5}", airfoil.lift_coefficient, airfoil.wave_drag_coefficient);
+            assert!((airfoil.lift_coefficient - 4.0 * angle_of_attack / beta).abs() / airfoil.lift_coefficient < 0.02);
+            assert!((airfoil.wave_drag_coefficient - 4.0 * (angle_of_attack.powi(2) + 0.01) / beta).abs() / airfoil.wave_drag_coefficient < 0.02);
+        }
+        Err(e) => {
+            panic!("thin airfoil failed: {:?}", e);
+        }
+    }
+
+    let ackeret = thinairfoil::compare_with_shock_expansion(&diamond, 2.0, angle_of_attack, ThinAirfoilTheory::Ackeret, specific_heat_ratio).expect("comparison failed");
+    let busemann = thinairfoil::compare_with_shock_expansion(&diamond, 2.0, angle_of_attack, ThinAirfoilTheory::Busemann, specific_heat_ratio).expect("comparison failed");
+    println!("{:?}\n{:?}", ackeret, busemann);
+    assert!(busemann.max_pressure_coefficient_error < 0.2 * ackeret.max_pressure_coefficient_error);
+    assert!(busemann.moment_coefficient_error.abs() < ackeret.moment_coefficient_error.abs());
+
+    // close to mach 1 the second order term pushes the compression side past
+    // the stagnation pressure, the panel keeps its Cp but has no mach number
+    let airfoil = ThinAirfoil::new(&diamond, 1.05, 0.0, ThinAirfoilTheory::Busemann, specific_heat_ratio).expect("thin airfoil failed");
+    assert!(airfoil.upper_panels[0].mach_number.is_none() && airfoil.upper_panels[0].pressure_coefficient.is_finite());
+    assert!(airfoil.upper_panels.iter().chain(airfoil.lower_panels.iter()).all(|panel| panel.mach_number.is_none_or(f64::is_finite)));
+}
+
+#[test]
+fn test_critical_mach() {
+    // anderson, fundamentals of aerodynamics example 11.6, Cp0,min = -0.43 gives Mcr = 0.74
+    let specific_heat_ratio = 1.4;
+
+    match compressibility::calc_critical_mach(-0.43, CompressibilityCorrection::PrandtlGlauert, specific_heat_ratio) {
+        Ok(critical_mach) => {
+            let pressure_coefficient = compressibility::calc_compressible_pressure_coefficient(-0.43, critical_mach, CompressibilityCorrection::PrandtlGlauert, specific_heat_ratio).expect("correction failed");
+            let critical_pressure_coefficient = compressibility::calc_critical_pressure_coefficient(critical_mach, specific_heat_ratio).expect("cp* failed");
+            println!("Mcr {:.4}, Cp {:.4}, Cp* {:.4}", critical_mach, pressure_coefficient, critical_pressure_coefficient);
+            assert!((critical_mach - 0.74).abs() < 0.005);
+            assert!((pressure_coefficient - critical_pressure_coefficient).abs() < 1e-6);
+        }
+        Err(e) => {
+            panic!("critical mach failed: {:?}", e);
+        }
+    }
+
+    // the nonlinear corrections grow faster with mach number and go critical earlier
+    let karman_tsien = compressibility::calc_critical_mach(-0.43, CompressibilityCorrection::KarmanTsien, specific_heat_ratio).expect("critical mach failed");
+    let laitone = compressibility::calc_critical_mach(-0.43, CompressibilityCorrection::Laitone, specific_heat_ratio).expect("critical mach failed");
+    assert!(laitone < karman_tsien && karman_tsien < 0.74);
+}
+
+#[test]
+fn test_pitot() {
+    // rayleigh pitot formula at mach 2 gives p02 / p1 = 5.6405 for air
+    let specific_heat_ratio = 1.4;
+
+    match pitot::calc_pitot_pressure_ratio_from_mach(2.0, specific_heat_ratio) {
+        Ok(pitot_pressure_ratio) => {
+            println!("p02 / p1 {:.4}", pitot_pressure_ratio);
+            assert!((pitot_pressure_ratio - 5.6405).abs() < 1e-4);
+        }
+        Err(e) => {
+            panic!("pitot failed: {:?}", e);
+        }
+    }
+
+    // the inverse has to pick the right branch on both sides of mach 1
+    for mach_number in [0.3, 0.99, 1.01, 2.0, 6.0] {
+        let pitot_pressure_ratio = pitot::calc_pitot_pressure_ratio_from_mach(mach_number, specific_heat_ratio).expect("pitot failed");
+        let recovered_mach = pitot::calc_mach_from_pitot_pressure_ratio(pitot_pressure_ratio, specific_heat_ratio).expect("inverse pitot failed");
+        println!("M {:.2} -> {:.4} -> {:.6}", mach_number, pitot_pressure_ratio, recovered_mach);
+        assert!((recovered_mach - mach_number).abs() < 1e-8);
+    }
+}
+
+#[test]
+fn test_air_data() {
+    // mach 0.8 at 10 km in the standard atmosphere, TAS 239.57, CAS 146.98, EAS 139.05 m/s,
+    // R from gas::AIR is 287.0550 against the 287.05287 of the standard so the
+    // tabulated pressure is only matched to a fraction of a pascal
+    let atmosphere = StandardAtmosphere::from_altitude(10000.0).expect("atmosphere failed");
+    assert!((atmosphere.pressure - 26436.3).abs() < 0.5);
+    assert!((atmosphere.temperature - 223.15).abs() < 1e-9);
+
+    let impact_pressure = airdata::calc_impact_pressure_from_mach(0.8, atmosphere.pressure).expect("impact pressure failed");
+    let total_air_temperature = airdata::calc_total_air_temperature(atmosphere.temperature, 0.8, Some(0.98)).expect("tat failed");
+    match AirData::from_pitot_static(impact_pressure, atmosphere.pressure, total_air_temperature, Some(0.98)) {
+        Ok(air_data) => {
+            println!("{:?}", air_data);
+            assert!((air_data.mach_number - 0.8).abs() < 1e-9);
+            assert!((air_data.true_airspeed - 239.57).abs() < 0.01);
+            assert!((air_data.calibrated_airspeed - 146.98).abs() < 0.01);
+            assert!((air_data.equivalent_airspeed - 139.05).abs() < 0.01);
+            assert!((air_data.pressure_altitude - 10000.0).abs() < 1e-6);
+        }
+        Err(e) => {
+            panic!("air data failed: {:?}", e);
+        }
+    }
+
+    // supersonic at sea level calibrated and true airspeed coincide
+    let true_airspeed = 2.0 * airdata::calc_speed_of_sound(airdata::SEA_LEVEL_TEMPERATURE);
+    let calibrated_airspeed = airdata::calc_calibrated_from_true_airspeed(true_airspeed, airdata::SEA_LEVEL_PRESSURE, airdata::SEA_LEVEL_TEMPERATURE).expect("cas failed");
+    assert!((calibrated_airspeed - true_airspeed).abs() < 1e-6);
+    let recovered_airspeed = airdata::calc_true_airspeed_from_calibrated(calibrated_airspeed, 5474.9, 216.65).expect("tas failed");
+    let roundtrip_airspeed = airdata::calc_calibrated_from_true_airspeed(recovered_airspeed, 5474.9, 216.65).expect("cas failed");
+    assert!((roundtrip_airspeed - calibrated_airspeed).abs() < 1e-6);
+
+    // a static pressure above sea level standard sits below sea level, the
+    // extended troposphere gives -138.5 m and 289.05 K for 103000 Pa
+    let pressure_altitude = airdata::calc_pressure_altitude(103000.0).expect("pressure altitude failed");
+    println!("103000 Pa at {:.2} m", pressure_altitude);
+    assert!((pressure_altitude + 138.5).abs() < 0.1);
+    let atmosphere = StandardAtmosphere::from_altitude(pressure_altitude).expect("atmosphere failed");
+    assert!((atmosphere.pressure - 103000.0).abs() < 1e-6);
+    assert!((atmosphere.temperature - 289.05).abs() < 0.01);
+    let air_data = AirData::from_pitot_static(impact_pressure, 103000.0, total_air_temperature, Some(0.98)).expect("air data failed");
+    assert!((air_data.pressure_altitude - pressure_altitude).abs() < 1e-9);
+    assert!(StandardAtmosphere::from_altitude(-2500.0).is_err());
+}
+
+#[test]
+fn test_shock_entropy_and_energy() {
+    // mach 2 normal shock, Δs / R = -ln(0.72087), u2 / u1 = 0.375 and the
+    // hugoniot energy term equals (p2 / p1 + 1) (1 - v2 / v1) / 2
+    let specific_heat_ratio = 1.4;
+    let outputs = [
+        normalshock::Output::EntropyChange,
+        normalshock::Output::StagnationTemperatureRatio,
+        normalshock::Output::VelocityRatio,
+        normalshock::Output::InternalEnergyChange,
+        normalshock::Output::PressureRatio,
+    ];
+    let values: Vec<f64> = outputs.into_iter()
+        .map(|output| normalshock::calculate(normalshock::Input::UpstreamMachNumber(2.0), output, specific_heat_ratio).expect("normal shock failed"))
+        .collect();
+    println!("{:?}", values);
+    assert!((values[0] + 0.72087_f64.ln()).abs() < 1e-5);
+    assert!((values[1] - 1.0).abs() < 1e-12);
+    assert!((values[2] - 0.375).abs() < 1e-12);
+    assert!((values[3] - (values[4] + 1.0) * (1.0 - values[2]) / 2.0).abs() < 1e-12);
+
+    // inverses back to the upstream mach number
+    for input in [
+        normalshock::Input::EntropyChange(values[0]),
+        normalshock::Input::VelocityRatio(values[2]),
+        normalshock::Input::InternalEnergyChange(values[3]),
+        normalshock::Input::EnthalpyChange(specific_heat_ratio * values[3]),
+    ] {
+        let upstream_mach = normalshock::calculate(input, normalshock::Output::UpstreamMachNumber, specific_heat_ratio).expect("inverse failed");
+        assert!((upstream_mach - 2.0).abs() < 1e-6);
+    }
+
+    // an oblique shock carries the entropy rise of its normal component
+    let inputs = vec![obliqueshock::Input::UpstreamMach(3.0), obliqueshock::Input::EntropyChange(values[0])];
+    let normal_upstream_mach = obliqueshock::calculate(inputs, obliqueshock::Output::NormalUpstreamMach, specific_heat_ratio).expect("oblique shock failed");
+    assert!((normal_upstream_mach - 2.0).abs() < 1e-6);
+
+    // a cone shock is an oblique shock at the same angle, the rise in entropy
+    // then holds unchanged through the isentropic compression up to the surface
+    let cone_outputs = [
+        taylormaccoll::Output::EntropyChange,
+        taylormaccoll::Output::StagnationTemperatureRatio,
+        taylormaccoll::Output::VelocityRatio,
+        taylormaccoll::Output::InternalEnergyChange,
+        taylormaccoll::Output::EnthalpyChange,
+        taylormaccoll::Output::StagnationPressureRatio,
+    ];
+    let cone_values: Vec<f64> = cone_outputs.into_iter()
+        .map(|output| taylormaccoll::calculate(vec![taylormaccoll::Input::UpstreamMach(4.0), taylormaccoll::Input::ShockAngle(PI / 6.0)], output, specific_heat_ratio).expect("cone failed"))
+        .collect();
+    let oblique_outputs = [
+        obliqueshock::Output::EntropyChange,
+        obliqueshock::Output::StagnationTemperatureRatio,
+        obliqueshock::Output::VelocityRatio,
+        obliqueshock::Output::InternalEnergyChange,
+        obliqueshock::Output::EnthalpyChange,
+        obliqueshock::Output::StagnationPressureRatio,
+    ];
+    let oblique_values: Vec<f64> = oblique_outputs.into_iter()
+        .map(|output| obliqueshock::calculate(vec![obliqueshock::Input::UpstreamMach(4.0), obliqueshock::Input::ShockAngle(PI / 6.0)], output, specific_heat_ratio).expect("oblique shock failed"))
+        .collect();
+    println!("{:?}\n{:?}", cone_values, oblique_values);
+    for (cone_value, oblique_value) in cone_values.iter().zip(oblique_values.iter()) {
+        assert!((cone_value - oblique_value).abs() < 1e-12);
+    }
+    assert!((cone_values[0] + cone_values[5].ln()).abs() < 1e-12);
+    assert!((cone_values[1] - 1.0).abs() < 1e-12);
+    assert!((cone_values[4] - specific_heat_ratio * cone_values[3]).abs() < 1e-12);
+
+    // the same cone found from its half angle
+    let inputs = vec![taylormaccoll::Input::UpstreamMach(4.0), taylormaccoll::Input::ConeAngle(23.49_f64.to_radians())];
+    let surface_pressure_ratio = taylormaccoll::calculate(inputs, taylormaccoll::Output::SurfacePressureRatio, specific_heat_ratio).expect("cone failed");
+    assert!((surface_pressure_ratio - 5.01).abs() < 0.01);
+    let inputs = vec![taylormaccoll::Input::UpstreamMach(4.0), taylormaccoll::Input::SurfaceMachNumber(2.66)];
+    assert!(taylormaccoll::calculate(inputs, taylormaccoll::Output::ConeAngle, specific_heat_ratio).is_err());
+}
+
+#[test]
+fn test_hugoniot() {
+    // the rayleigh line for a mach 3 shock crosses the hugoniot at the normal
+    // shock state, and the isentrope touches the hugoniot at the initial state
+    let specific_heat_ratio = 1.4;
+    let (volume_ratio, pressure_ratio) = hugoniot::calc_shock_state(3.0, specific_heat_ratio).expect("shock state failed");
+    let hugoniot_pressure_ratio = hugoniot::calc_hugoniot_pressure_ratio(volume_ratio, specific_heat_ratio).expect("hugoniot failed");
+    let rayleigh_pressure_ratio = hugoniot::calc_rayleigh_line_pressure_ratio(volume_ratio, 3.0, specific_heat_ratio).expect("rayleigh line failed");
+    println!("v2 / v1 {:.5}, p2 / p1 {:.5}, hugoniot {:.5}, rayleigh {:.5}", volume_ratio, pressure_ratio, hugoniot_pressure_ratio, rayleigh_pressure_ratio);
+    assert!((hugoniot_pressure_ratio - pressure_ratio).abs() < 1e-9);
+    assert!((rayleigh_pressure_ratio - pressure_ratio).abs() < 1e-9);
+
+    match (
+        hugoniot::calc_hugoniot_curve(0.9, 1.1, 41, specific_heat_ratio),
+        hugoniot::calc_isentrope(0.9, 1.1, 41, specific_heat_ratio),
+    ) {
+        (Ok(hugoniot_curve), Ok(isentrope)) => {
+            // second order contact, the gap shrinks with the cube of the volume change
+            let gap = |i: usize| (hugoniot_curve[i].1 - isentrope[i].1).abs();
+            assert!(gap(20) < 1e-12);
+            assert!(gap(30) / gap(40) < 0.2);
+            assert!(hugoniot_curve[0].1 > isentrope[0].1);
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            panic!("curves failed: {:?}", e);
+        }
+    }
+}
+
+#[test]
+fn test_chapman_jouguet() {
+    // γ = 1.2 and Q = 50 give MCJ = 6.216, pCJ / p1 = (1 + γ MCJ²) / (γ + 1)
+    // and a sonic downstream state in the wave frame
+    let specific_heat_ratio = 1.2;
+    let heat_release = 50.0;
+
+    match detonation::calc_chapman_jouguet_state(heat_release, specific_heat_ratio, true) {
+        Ok(state) => {
+            println!("{:?}", state);
+            assert!((state.wave_mach - 6.2162).abs() < 1e-4);
+            assert!((state.pressure_ratio - (1.0 + specific_heat_ratio * state.wave_mach.powi(2)) / (specific_heat_ratio + 1.0)).abs() < 1e-9);
+            assert!((state.downstream_mach - 1.0).abs() < 1e-9);
+            let von_neumann = detonation::calc_von_neumann_state(state.wave_mach, specific_heat_ratio).expect("von neumann failed");
+            assert!(von_neumann.pressure_ratio > 1.9 * state.pressure_ratio);
+        }
+        Err(e) => {
+            panic!("chapman jouguet failed: {:?}", e);
+        }
+    }
+
+    // overdriven detonations split into a subsonic strong and a supersonic weak state
+    let strong = detonation::calc_overdriven_detonation_state(1.2, heat_release, DetonationBranch::Strong, specific_heat_ratio).expect("strong failed");
+    let weak = detonation::calc_overdriven_detonation_state(1.2, heat_release, DetonationBranch::Weak, specific_heat_ratio).expect("weak failed");
+    assert!(strong.downstream_mach < 1.0 && weak.downstream_mach > 1.0);
+    assert!(strong.pressure_ratio > weak.pressure_ratio);
+
+    // with no heat release the strong branch is the normal shock
+    let shock = detonation::calc_detonation_state(3.0, 0.0, DetonationBranch::Strong, specific_heat_ratio).expect("shock failed");
+    let von_neumann = detonation::calc_von_neumann_state(3.0, specific_heat_ratio).expect("von neumann failed");
+    assert!((shock.pressure_ratio - von_neumann.pressure_ratio).abs() < 1e-9);
+
+    let deflagration = detonation::calc_chapman_jouguet_state(heat_release, specific_heat_ratio, false).expect("deflagration failed");
+    assert!(deflagration.wave_mach < 1.0 && deflagration.density_ratio < 1.0);
+    assert!((deflagration.downstream_mach - 1.0).abs() < 1e-9);
+
+    // nothing steady between the two chapman jouguet speeds
+    assert!(detonation::calc_detonation_state(2.0, heat_release, DetonationBranch::Strong, specific_heat_ratio).is_err());
+}
+
+#[test]
+fn test_dormand_prince() {
+    // y'' = -y from (1, 0) over a full period returns to the start
+    let f = |_x: f64, y: &[f64]| vec![y[1], -y[0]];
+    let (xs, ys) = numerics::dormand_prince(&f, 0.0, &[1.0, 0.0], 2.0 * PI, Some(1e-10), None).expect("dormand-prince failed");
+    let last = ys.last().expect("no steps");
+    println!("{} steps, y = {:?}", xs.len(), last);
+    assert!((xs[xs.len() - 1] - 2.0 * PI).abs() < 1e-12);
+    assert!((last[0] - 1.0).abs() < 1e-8 && last[1].abs() < 1e-8);
+
+    // a right hand side that stops being finite is an error rather than a panic
+    let f = |x: f64, _y: &[f64]| vec![if x < 1.0 { 1.0 } else { f64::NAN }];
+    assert!(matches!(numerics::dormand_prince(&f, 0.0, &[0.0], 2.0, None, None), Err(IsentropicFlowError::MathError)));
+}
+
+#[test]
+fn test_znd_detonation() {
+    // the reaction zone starts at the von neumann spike and relaxes to the cj
+    // pressure (1 + γ MCJ²) / (γ + 1) once the mixture has burnt
+    let specific_heat_ratio = 1.2;
+
+    match ZndDetonation::new(1.0, 50.0, 10.0, 100.0, specific_heat_ratio, None, None) {
+        Ok(znd) => {
+            let last = znd.distance.len() - 1;
+            let chapman_jouguet_pressure_ratio = (1.0 + specific_heat_ratio * znd.wave_mach.powi(2)) / (specific_heat_ratio + 1.0);
+            println!("induction length {:.5}, λ {:.6}, p {:.4}, pCJ {:.4}", znd.induction_length, znd.reaction_progress[last], znd.pressure_ratio[last], chapman_jouguet_pressure_ratio);
+            assert!((znd.pressure_ratio[0] - znd.von_neumann_state.pressure_ratio).abs() < 1e-9);
+            assert!((znd.pressure_ratio[last] - chapman_jouguet_pressure_ratio).abs() / chapman_jouguet_pressure_ratio < 0.01);
+            assert!(znd.pressure_ratio.windows(2).all(|pair| pair[1] <= pair[0]));
+            assert!(znd.induction_length > 0.0 && znd.induction_length < znd.distance[last]);
+
+            // the induction length should not depend on the step limit
+            let refined = ZndDetonation::new(1.0, 50.0, 10.0, 100.0, specific_heat_ratio, None, Some(0.01)).expect("znd failed");
+            assert!((refined.induction_length - znd.induction_length).abs() / znd.induction_length < 0.01);
+
+            // at the cj speed the burnt end of the zone is the sonic point, the
+            // rate has to stay finite up to it
+            let sonic_mach = znd.velocity[last] / znd.temperature_ratio[last].sqrt();
+            println!("λ {:.9}, M {:.6}", znd.reaction_progress[last], sonic_mach);
+            assert!(znd.reaction_progress[last] > 1.0 - 1e-6);
+            assert!((sonic_mach - 1.0).abs() < 1e-2);
+        }
+        Err(e) => {
+            panic!("znd failed: {:?}", e);
+        }
+    }
+}
+
+#[test]
+fn test_vibrational_relaxation() {
+    // T falls and Tv rises from the frozen shock state until they meet, with the
+    // mass, momentum and energy fluxes held at their upstream values
+    let (upstream_temperature, upstream_pressure) = (300.0, 1000.0);
+
+    match VibrationalRelaxation::behind_normal_shock(NITROGEN, 8.0, upstream_temperature, upstream_pressure, 0.5, None, None) {
+        Ok(relaxation) => {
+            let last = relaxation.distance.len() - 1;
+            let equilibrium = relaxation.equilibrium_state;
+            println!("frozen {:?}\nequilibrium {:?}", relaxation.frozen_state, equilibrium);
+            assert!((relaxation.temperature[0] - relaxation.frozen_state.temperature).abs() < 1e-6);
+            assert!((relaxation.temperature[last] - equilibrium.temperature).abs() / equilibrium.temperature < 1e-4);
+            assert!((relaxation.vibrational_temperature[last] - equilibrium.vibrational_temperature).abs() / equilibrium.temperature < 1e-4);
+            // allowing for rounding once both have settled
+            assert!(relaxation.temperature.windows(2).all(|pair| pair[1] <= pair[0] + 1e-6));
+            assert!(relaxation.vibrational_temperature.windows(2).all(|pair| pair[1] >= pair[0] - 1e-6));
+
+            let r = NITROGEN.gas_constant();
+            let upstream_density = upstream_pressure / (r * upstream_temperature);
+            let upstream_velocity = 8.0 * (1.4 * r * upstream_temperature).sqrt();
+            let mass_flux = upstream_density * upstream_velocity;
+            let enthalpy = |temperature: f64, vibrational_energy: f64, velocity: f64| 3.5 * r * temperature + vibrational_energy + velocity.powi(2) / 2.0;
+            assert!((equilibrium.density * equilibrium.velocity - mass_flux).abs() / mass_flux < 1e-9);
+            assert!((equilibrium.pressure + mass_flux * equilibrium.velocity - upstream_pressure - mass_flux * upstream_velocity).abs() / equilibrium.pressure < 1e-9);
+            let upstream_enthalpy = enthalpy(upstream_temperature, NITROGEN.vibrational_energy(upstream_temperature), upstream_velocity);
+            let equilibrium_enthalpy = enthalpy(equilibrium.temperature, NITROGEN.vibrational_energy(equilibrium.temperature), equilibrium.velocity);
+            assert!((equilibrium_enthalpy - upstream_enthalpy).abs() / upstream_enthalpy < 1e-6);
+        }
+        Err(e) => {
+            panic!("vibrational relaxation failed: {:?}", e);
+        }
+    }
+}
+
+#[test]
+fn test_equilibrium_normal_shock() {
+    // below the dissociation range the ideal dissociating gas is perfect with
+    // γ = 4/3, above it the shock compresses past the (γ + 1) / (γ - 1) = 7 limit
+    let (upstream_temperature, upstream_pressure) = (300.0, 100.0);
+    let perfect = GasModel::CaloricallyPerfect { molar_mass: NITROGEN.molar_mass, specific_heat_ratio: 4.0 / 3.0 };
+
+    match (EquilibriumNormalShock::new(GasModel::IdealDissociating(NITROGEN), 1000.0, upstream_temperature, upstream_pressure), EquilibriumNormalShock::new(perfect, 1000.0, upstream_temperature, upstream_pressure)) {
+        (Ok(dissociating), Ok(perfect)) => {
+            assert!(dissociating.dissociation_fraction < 1e-10);
+            assert!((dissociating.density_ratio - perfect.density_ratio).abs() / perfect.density_ratio < 1e-6);
+            assert!((dissociating.temperature - perfect.temperature).abs() / perfect.temperature < 1e-6);
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            panic!("equilibrium normal shock failed: {:?}", e);
+        }
+    }
+
+    for species in [NITROGEN, OXYGEN] {
+        match EquilibriumNormalShock::new(GasModel::IdealDissociating(species), 6000.0, upstream_temperature, upstream_pressure) {
+            Ok(shock) => {
+                println!("α {:.4}, T {:.1}, ρ2 / ρ1 {:.3}, p2 / p1 {:.1}", shock.dissociation_fraction, shock.temperature, shock.density_ratio, shock.pressure_ratio);
+                assert!(shock.dissociation_fraction > 0.1 && shock.density_ratio > 7.0);
+                let equilibrium = species.equilibrium_dissociation_fraction(shock.density, shock.temperature);
+                assert!((shock.dissociation_fraction - equilibrium).abs() < 1e-8);
+
+                let r = species.gas_constant();
+                let upstream_density = upstream_pressure / (r * upstream_temperature);
+                assert!((shock.pressure - shock.density * r * (1.0 + shock.dissociation_fraction) * shock.temperature).abs() / shock.pressure < 1e-9);
+                assert!((shock.density * shock.velocity - upstream_density * 6000.0).abs() / (upstream_density * 6000.0) < 1e-9);
+                let upstream_total_enthalpy = 4.0 * r * upstream_temperature + 6000.0_f64.powi(2) / 2.0;
+                let total_enthalpy = r * ((4.0 + shock.dissociation_fraction) * shock.temperature + shock.dissociation_fraction * species.dissociation_temperature) + shock.velocity.powi(2) / 2.0;
+                assert!((total_enthalpy - upstream_total_enthalpy).abs() / upstream_total_enthalpy < 1e-9);
+            }
+            Err(e) => {
+                panic!("equilibrium normal shock failed: {:?}", e);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_mixture() {
+    // equal moles of air (cp = 7/2 Ru) and helium (cp = 5/2 Ru) give γ = 3/2
+    let air = Species { molar_mass: 28.9647e-3, heat_capacity: HeatCapacity::Constant(3.5) };
+    let helium = Species { molar_mass: 4.0026e-3, heat_capacity: HeatCapacity::Constant(2.5) };
+
+    match Mixture::from_mole_fractions(&[(air, 1.0), (helium, 1.0)]) {
+        Ok(mixture) => {
+            let specific_heat_ratio = mixture.specific_heat_ratio(None);
+            assert!((specific_heat_ratio - 1.5).abs() < 1e-12);
+            assert!((mixture.molar_mass - 0.5 * (28.9647e-3 + 4.0026e-3)).abs() < 1e-15);
+            assert!((mixture.specific_heat_cp(None) - mixture.specific_heat_cv(None) - mixture.gas_constant).abs() < 1e-9);
+
+            // the same blend by mass
+            let mass_fractions = mixture.mass_fractions();
+            let by_mass = Mixture::from_mass_fractions(&[(air, mass_fractions[0]), (helium, mass_fractions[1])]).expect("mixture failed");
+            assert!((by_mass.specific_heat_ratio(None) - specific_heat_ratio).abs() < 1e-12);
+
+            let normal_shock = NormalShock::from_upstream_mach(2.0, specific_heat_ratio).expect("normal shock failed");
+            assert!((normal_shock.pressure_ratio - (2.0 * specific_heat_ratio * 4.0 - (specific_heat_ratio - 1.0)) / (specific_heat_ratio + 1.0)).abs() < 1e-9);
+            IsentropicFlow::from_mach(2.0, specific_heat_ratio).expect("isentropic flow failed");
+        }
+        Err(e) => {
+            panic!("mixture failed: {:?}", e);
+        }
+    }
+
+    // thermally perfect carbon dioxide, 200 K to 1000 K nasa polynomial
+    let carbon_dioxide = Species {
+        molar_mass: 44.0095e-3,
+        heat_capacity: HeatCapacity::Polynomial([2.35677352, 8.98459677e-3, -7.12356269e-6, 2.45919022e-9, -1.43699548e-13]),
+    };
+    let mixture = Mixture::from_mass_fractions(&[(carbon_dioxide, 1.0)]).expect("mixture failed");
+    println!("γ {:.4} at 300 K, {:.4} at 1000 K", mixture.specific_heat_ratio(Some(300.0)), mixture.specific_heat_ratio(Some(1000.0)));
+    assert!((mixture.specific_heat_ratio(Some(300.0)) - 1.288).abs() < 0.002);
+    assert!(mixture.specific_heat_ratio(Some(1000.0)) < mixture.specific_heat_ratio(Some(300.0)));
+    assert!(Mixture::from_mole_fractions(&[(air, -1.0)]).is_err());
+}
+
+#[test]
+fn test_gas_catalogue() {
+    match gas::from_name("Carbon_Dioxide") {
+        Ok(carbon_dioxide) => {
+            assert_eq!(carbon_dioxide.formula, "co2");
+            assert!((carbon_dioxide.gas_constant() - 188.92).abs() < 0.01);
+        }
+        Err(e) => {
+            panic!("gas lookup failed: {:?}", e);
+        }
+    }
+    assert!(gas::from_name("unobtainium").is_err());
+
+    // sutherland air viscosity at 300 K
+    let air = gas::from_name("AIR").expect("gas lookup failed");
+    assert!((air.viscosity(300.0) - 1.846e-5).abs() < 0.005e-5);
+
+    // a name works wherever γ does
+    let specific_heat_ratio = gas::specific_heat_ratio("he").expect("gas lookup failed");
+    let pressure_ratio = isentropic::calculate(isentropic::Output::PressureRatio, isentropic::Input::MachNumber(2.0), Some(specific_heat_ratio)).expect("isentropic failed");
+    assert!((pressure_ratio - (1.0 + (specific_heat_ratio - 1.0) / 2.0 * 4.0).powf(-specific_heat_ratio / (specific_heat_ratio - 1.0))).abs() < 1e-12);
+
+    // or the catalogue entry goes straight into the calculators
+    let helium = gas::from_name("helium").expect("gas lookup failed");
+    let gas_pressure_ratio = isentropic::calculate_for_gas(isentropic::Output::PressureRatio, isentropic::Input::MachNumber(2.0), helium).expect("isentropic failed");
+    assert!((gas_pressure_ratio - pressure_ratio).abs() < 1e-12);
+    let shock_pressure_ratio = normalshock::calculate_for_gas(normalshock::Input::UpstreamMachNumber(2.0), normalshock::Output::PressureRatio, helium).expect("normal shock failed");
+    assert!((shock_pressure_ratio - (2.0 * specific_heat_ratio * 4.0 - (specific_heat_ratio - 1.0)) / (specific_heat_ratio + 1.0)).abs() < 1e-12);
+    let inputs = vec![obliqueshock::Input::UpstreamMach(4.0), obliqueshock::Input::ShockAngle(PI / 6.0)];
+    let oblique_pressure_ratio = obliqueshock::calculate_for_gas(inputs, obliqueshock::Output::PressureRatio, helium).expect("oblique shock failed");
+    let inputs = vec![taylormaccoll::Input::UpstreamMach(4.0), taylormaccoll::Input::ShockAngle(PI / 6.0)];
+    let cone_pressure_ratio = taylormaccoll::calculate_for_gas(inputs, taylormaccoll::Output::PressureRatio, helium).expect("cone failed");
+    assert!((oblique_pressure_ratio - cone_pressure_ratio).abs() < 1e-12);
+    assert!((oblique_pressure_ratio - normalshock::calc_pressure_ratio_from_upstream_mach(2.0, specific_heat_ratio).unwrap()).abs() < 1e-9);
+    let mixture = Mixture::from_mole_fractions(&[(air.species(), 0.5), (gas::from_name("helium").unwrap().species(), 0.5)]).expect("mixture failed");
+    assert!((mixture.specific_heat_ratio(None) - 1.5).abs() < 1e-12);
+}
+
+#[test]
+fn test_liquid_shock() {
+    // with p∞ = 0 the stiffened gas is a perfect gas
+    let gas = LiquidModel::StiffenedGas { specific_heat_ratio: 1.4, stiffness_pressure: 0.0 };
+    match liquid::calculate(liquid::Input::UpstreamMachNumber(3.0), liquid::Output::PressureRatio, gas, 1e5, 1.2) {
+        Ok(pressure_ratio) => {
+            let expected = normalshock::calc_pressure_ratio_from_upstream_mach(3.0, 1.4).unwrap();
+            assert!((pressure_ratio - expected).abs() / expected < 1e-9);
+        }
+        Err(e) => {
+            panic!("liquid shock failed: {:?}", e);
+        }
+    }
+
+    // a 1 GPa shock in water runs at about 2.3 km/s in both models, and every
+    // input gives back the same shock
+    for model in [WATER_STIFFENED_GAS, WATER_TAIT] {
+        match LiquidShock::new(liquid::Input::Pressure(1e9), model, 1e5, 1000.0) {
+            Ok(shock) => {
+                println!("{:?}", shock);
+                assert!((shock.shock_speed - 2300.0).abs() < 100.0);
+                for input in [
+                    liquid::Input::ShockSpeed(shock.shock_speed),
+                    liquid::Input::ParticleVelocity(shock.particle_velocity),
+                    liquid::Input::UpstreamMachNumber(shock.upstream_mach),
+                    liquid::Input::DensityRatio(shock.density_ratio),
+                ] {
+                    let pressure = liquid::calculate(input, liquid::Output::Pressure, model, 1e5, 1000.0).expect("liquid shock failed");
+                    assert!((pressure - 1e9).abs() / 1e9 < 1e-8);
+                }
+            }
+            Err(e) => {
+                panic!("liquid shock failed: {:?}", e);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_liquid_riemann_problem() {
+    // toro's sod test for a perfect gas, p* = 0.30313 and u* = 0.92745
+    let gas = LiquidModel::StiffenedGas { specific_heat_ratio: 1.4, stiffness_pressure: 0.0 };
+    let left = LiquidState { density: 1.0, velocity: 0.0, pressure: 1.0 };
+    let right = LiquidState { density: 0.125, velocity: 0.0, pressure: 0.1 };
+    match liquid::solve_riemann_problem(left, gas, right, gas) {
+        Ok(solution) => {
+            assert!((solution.pressure - 0.30313).abs() < 1e-5);
+            assert!((solution.velocity - 0.92745).abs() < 1e-5);
+            assert!((solution.left_density - 0.42632).abs() < 1e-5);
+            assert!((solution.right_density - 0.26557).abs() < 1e-5);
+            assert!(matches!(solution.left_wave, RiemannWave::Rarefaction(_, _)));
+            assert!(matches!(solution.right_wave, RiemannWave::Shock(speed) if (speed - 1.75216).abs() < 1e-5));
+            let sampled = solution.sample(0.5).expect("sample failed");
+            assert!((sampled.pressure - solution.pressure).abs() < 1e-12);
+        }
+        Err(e) => {
+            panic!("riemann problem failed: {:?}", e);
+        }
+    }
+
+    // two water jets colliding at ±200 m/s stop behind shocks of that particle velocity
+    let left = LiquidState { density: 1000.0, velocity: 200.0, pressure: 1e5 };
+    let right = LiquidState { density: 1000.0, velocity: -200.0, pressure: 1e5 };
+    let solution = liquid::solve_riemann_problem(left, WATER_TAIT, right, WATER_TAIT).expect("riemann problem failed");
+    let pressure = liquid::calculate(liquid::Input::ParticleVelocity(200.0), liquid::Output::Pressure, WATER_TAIT, 1e5, 1000.0).expect("liquid shock failed");
+    assert!(solution.velocity.abs() < 1e-6);
+    assert!((solution.pressure - pressure).abs() / pressure < 1e-8);
+}
+
+#[test]
+fn test_real_gas() {
+    // dilute nitrogen is a perfect gas with γ = 1.4, so Γ = (γ + 1) / 2
+    let nitrogen = CubicGas {
+        equation_of_state: CubicEquationOfState::PengRobinson,
+        molar_mass: 28.0134e-3,
+        critical_temperature: 126.19,
+        critical_pressure: 3.3958e6,
+        acentric_factor: 0.0372,
+        ideal_heat_capacity: 2.5,
+    };
+    match nitrogen.state(300.0, 1e3) {
+        Ok(state) => {
+            assert!((state.speed_of_sound - (1.4 * nitrogen.gas_constant() * 300.0).sqrt()).abs() < 0.01);
+            assert!((state.fundamental_derivative - 1.2).abs() < 1e-4);
+            assert!(!state.is_non_classical());
+        }
+        Err(e) => {
+            panic!("real gas state failed: {:?}", e);
+        }
+    }
+    let shock = RealGasShock::from_upstream_mach(nitrogen, 2.0, 300.0, 1e3).expect("real gas shock failed");
+    assert!((shock.pressure_ratio - normalshock::calc_pressure_ratio_from_upstream_mach(2.0, 1.4).unwrap()).abs() < 1e-4);
+    assert!(shock.entropy_change > 0.0);
+    let pressure_ratio = isentropic::calc_pressure_ratio_from_mach(2.0, 1.4).unwrap();
+    let expansion = nitrogen.isentropic_expansion(300.0, 1e4, 1e4 * pressure_ratio).expect("real gas expansion failed");
+    assert!((expansion.mach_number - 2.0).abs() < 1e-4);
+
+    // a heavy siloxane vapour near its critical point only turns non classical
+    // when its molecules carry enough heat capacity
+    for (ideal_heat_capacity, non_classical) in [(100.0, true), (4.0, false)] {
+        let siloxane = CubicGas {
+            equation_of_state: CubicEquationOfState::VanDerWaals,
+            molar_mass: 444.924e-3,
+            critical_temperature: 645.78,
+            critical_pressure: 0.961e6,
+            acentric_factor: 0.736,
+            ideal_heat_capacity,
+        };
+        let state = siloxane.state(626.4, 8.65e5).expect("real gas state failed");
+        println!("cv / R {}, Γ {:.4}, Z {:.4}", ideal_heat_capacity, state.fundamental_derivative, state.compressibility_factor);
+        assert_eq!(state.is_non_classical(), non_classical);
+    }
+}
+
+#[test]
+fn test_flat_plate_boundary_layer() {
+    let air = gas::from_name("air").expect("gas lookup failed");
+    let (temperature, pressure) = (220.0, 2e4);
+
+    // blasius at low speed on an unheated wall
+    let edge = EdgeConditions::new(0.05, temperature, pressure, air).expect("edge conditions failed");
+    let distance = 1e6 / edge.unit_reynolds_number();
+    match FlatPlateBoundaryLayer::new(&edge, distance, temperature, BoundaryLayerRegime::Laminar, SkinFrictionMethod::ReferenceTemperature, None) {
+        Ok(boundary_layer) => {
+            assert!((boundary_layer.skin_friction_coefficient - 0.664e-3).abs() / 0.664e-3 < 0.01);
+        }
+        Err(e) => {
+            panic!("boundary layer failed: {:?}", e);
+        }
+    }
+    assert!(FlatPlateBoundaryLayer::new(&edge, distance, temperature, BoundaryLayerRegime::Laminar, SkinFrictionMethod::VanDriestII, None).is_err());
+
+    // van driest II drops the adiabatic turbulent cf by about a fifth at mach 2,
+    // and an adiabatic wall takes no heat
+    let skin_friction = |mach: f64| {
+        let edge = EdgeConditions::new(mach, temperature, pressure, air).expect("edge conditions failed");
+        let recovery_temperature = boundarylayer::calc_recovery_temperature(temperature, mach, BoundaryLayerRegime::Turbulent, None, air.specific_heat_ratio);
+        let boundary_layer = FlatPlateBoundaryLayer::new(&edge, 1e7 / edge.unit_reynolds_number(), recovery_temperature, BoundaryLayerRegime::Turbulent, SkinFrictionMethod::VanDriestII, None).expect("boundary layer failed");
+        assert!(boundary_layer.heat_flux.abs() < 1e-9);
+        boundary_layer.skin_friction_coefficient
+    };
+    let ratio = skin_friction(2.0) / skin_friction(0.05);
+    println!("cf / cf_inc {:.4}", ratio);
+    assert!((ratio - 0.78).abs() < 0.03);
+
+    // edge conditions on a wedge, the cold wall is heated
+    let shock = ObliqueShock::from_mach_and_deflection_angle(5.0, 10.0_f64.to_radians(), 1.4).expect("oblique shock failed");
+    let edge = EdgeConditions::behind_oblique_shock(&shock, temperature, pressure, air).expect("edge conditions failed");
+    let boundary_layer = FlatPlateBoundaryLayer::new(&edge, 0.5, 300.0, BoundaryLayerRegime::Turbulent, SkinFrictionMethod::ReferenceTemperature, None).expect("boundary layer failed");
+    assert!(boundary_layer.heat_flux > 0.0 && boundary_layer.recovery_temperature > boundary_layer.reference_temperature);
+
+    // on a cone the edge takes the surface state of the cone tables, mach 4 behind
+    // a 30 degree shock gives Mc = 2.66, pc / p1 = 5.01 and Tc / T1 = 1.740, and
+    // the edge velocity holds the freestream total enthalpy
+    let cone = taylormaccoll::SupersonicCone::from_mach_and_shock_angle(4.0, PI / 6.0, 1.4).expect("cone failed");
+    match EdgeConditions::on_cone_surface(&cone, temperature, pressure, air) {
+        Ok(edge) => {
+            println!("{:?}", edge);
+            assert!((edge.mach_number - 2.66).abs() < 0.01);
+            assert!((edge.pressure / pressure - 5.01).abs() < 0.01);
+            assert!((edge.temperature / temperature - 1.740).abs() < 0.005);
+            let specific_heat = air.specific_heat_ratio / (air.specific_heat_ratio - 1.0) * air.gas_constant();
+            let freestream_velocity = 4.0 * (air.specific_heat_ratio * air.gas_constant() * temperature).sqrt();
+            let total_enthalpy = specific_heat * temperature + freestream_velocity.powi(2) / 2.0;
+            assert!((specific_heat * edge.temperature + edge.velocity().powi(2) / 2.0 - total_enthalpy).abs() / total_enthalpy < 1e-9);
+        }
+        Err(e) => {
+            panic!("edge conditions failed: {:?}", e);
+        }
+    }
+}
+
+#[test]
+fn test_similarity_boundary_layer() {
+    // with C = 1 and Pr = 1 a cold wall keeps blasius friction, cf √Re = 0.664,
+    // and follows the reynolds analogy St = cf / 2
+    match SimilarityBoundaryLayer::new(0.0, Some(0.5), 0.0, ViscosityLaw::ChapmanRubesin(1.0), Some(1.0), 1.4, None) {
+        Ok(boundary_layer) => {
+            let stanton_number = boundary_layer.stanton_number.expect("no stanton number");
+            assert!((boundary_layer.skin_friction - 0.664).abs() < 1e-3);
+            assert!((stanton_number - boundary_layer.skin_friction / 2.0).abs() < 1e-6);
+            assert!((boundary_layer.velocity.last().unwrap() - 1.0).abs() < 1e-6);
+            assert!((boundary_layer.temperature[0] - 0.5).abs() < 1e-12);
+        }
+        Err(e) => {
+            panic!("similarity boundary layer failed: {:?}", e);
+        }
+    }
+
+    // an adiabatic wall at mach 3 recovers r ≈ √Pr of the stagnation temperature
+    let prandtl_number: f64 = 0.72;
+    let boundary_layer = SimilarityBoundaryLayer::new(3.0, None, 0.0, ViscosityLaw::ChapmanRubesin(1.0), Some(prandtl_number), 1.4, None).expect("similarity boundary layer failed");
+    let recovery_factor = (boundary_layer.wall_temperature_ratio - 1.0) / (0.2 * 9.0);
+    println!("recovery factor {:.5}", recovery_factor);
+    assert!((recovery_factor - prandtl_number.sqrt()).abs() < 2e-3);
+    assert!(boundary_layer.stanton_number.is_none());
+}
+
+#[test]
+fn test_stagnation_point_heating() {
+    // fay riddell and sutton graves agree within about ten percent for a
+    // cold wall sphere, and both scale with 1 / √Rn
+    match StagnationPointHeating::from_altitude(1.0, 5000.0, 30000.0, 300.0) {
+        Ok(heating) => {
+            let ratio = heating.fay_riddell_heat_flux / heating.sutton_graves_heat_flux;
+            println!("fay riddell {:.4e} sutton graves {:.4e}", heating.fay_riddell_heat_flux, heating.sutton_graves_heat_flux);
+            assert!((ratio - 1.0).abs() < 0.1);
+            let small_nose = StagnationPointHeating::from_altitude(0.25, 5000.0, 30000.0, 300.0).expect("stagnation heating failed");
+            assert!((small_nose.fay_riddell_heat_flux / heating.fay_riddell_heat_flux - 2.0).abs() < 1e-9);
+            assert!((small_nose.sutton_graves_heat_flux / heating.sutton_graves_heat_flux - 2.0).abs() < 1e-9);
+        }
+        Err(e) => {
+            panic!("stagnation point heating failed: {:?}", e);
+        }
+    }
+
+    let sutton_graves = stagnationheating::calc_sutton_graves_heat_flux(1.0, 7000.0, 0.0136, None).expect("sutton graves failed");
+    assert!((sutton_graves - 1.7415e-4 * 0.0136_f64.sqrt() * 7000.0_f64.powi(3)).abs() < 1e-6);
+}
+
+#[test]
+fn test_billig_shock() {
+    // the shock is normal on the axis and tends to the mach angle far away
+    let mach: f64 = 5.0;
+    match BilligShock::new(BluntBody::Sphere, mach, 0.5, 1.4) {
+        Ok(shock) => {
+            assert!((shock.standoff_distance - 0.5 * 0.143 * (3.24 / 25.0_f64).exp()).abs() < 1e-12);
+            let axis = shock.point(0.0).expect("axis point failed");
+            assert!(axis.x.abs() < 1e-12 && (axis.shock_angle - PI / 2.0).abs() < 1e-12);
+            assert!((axis.pressure_ratio - normalshock::calc_pressure_ratio_from_upstream_mach(mach, 1.4).unwrap()).abs() < 1e-9);
+            let far = shock.point(1e4).expect("far point failed");
+            assert!((far.shock_angle - (1.0 / mach).asin()).abs() < 1e-3);
+            let shape = shock.shape(2.0, 21).expect("shock shape failed");
+            assert!(shape.windows(2).all(|pair| pair[1].x > pair[0].x && pair[1].shock_angle < pair[0].shock_angle));
+        }
+        Err(e) => {
+            panic!("billig shock failed: {:?}", e);
+        }
+    }
+
+    // a cylinder stands its shock off further than a sphere
+    let sphere = bluntbody::calc_standoff_distance(BluntBody::Sphere, 3.0).unwrap();
+    let cylinder = bluntbody::calc_standoff_distance(BluntBody::Cylinder, 3.0).unwrap();
+    assert!(cylinder > 2.0 * sphere);
+}
+
+#[test]
+fn test_newtonian_stl() {
+    // the same tetrahedron written as ascii and as binary stl
+    let vertices: [[f64; 3]; 4] = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    let faces: [[usize; 3]; 4] = [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]];
+    let mut ascii: String = String::from("solid tetrahedron\n");
+    let mut binary: Vec<u8> = b"solid but actually binary".to_vec();
+    binary.resize(80, 0);
+    binary.extend_from_slice(&(faces.len() as u32).to_le_bytes());
+    for face in faces {
+        ascii.push_str("  facet normal 0 0 0\n    outer loop\n");
+        binary.extend_from_slice(&[0u8; 12]);
+        for vertex in face {
+            let [x, y, z] = vertices[vertex];
+            ascii.push_str(&format!("      vertex {} {} {}\n", x, y, z));
+            for coordinate in [x, y, z] {
+                binary.extend_from_slice(&(coordinate as f32).to_le_bytes());
+            }
+        }
+        ascii.push_str("    endloop\n  endfacet\n");
+        binary.extend_from_slice(&[0u8; 2]);
+    }
+    ascii.push_str("endsolid tetrahedron\n");
+    match (TriangleMesh::from_stl(ascii.as_bytes()), TriangleMesh::from_stl(&binary)) {
+        (Ok(from_ascii), Ok(from_binary)) => {
+            assert_eq!(from_ascii.triangles, from_binary.triangles);
+            assert!((from_ascii.surface_area() - (1.5 + 3.0_f64.sqrt() / 2.0)).abs() < 1e-12);
+        }
+        (ascii_result, binary_result) => {
+            panic!("stl parsing failed: {:?} {:?}", ascii_result.err(), binary_result.err());
+        }
+    }
+
+    // newtonian sphere, CD = Cp,max / 2 on the frontal area at any incidence
+    let (rings, sectors): (usize, usize) = (60, 120);
+    let point = |i: usize, j: usize| {
+        let (polar, azimuth) = (PI * i as f64 / rings as f64, 2.0 * PI * j as f64 / sectors as f64);
+        [polar.sin() * azimuth.cos(), polar.sin() * azimuth.sin(), polar.cos()]
+    };
+    let mut triangles: Vec<[[f64; 3]; 3]> = Vec::new();
+    for i in 0..rings {
+        for j in 0..sectors {
+            triangles.push([point(i, j), point(i + 1, j), point(i + 1, j + 1)]);
+            triangles.push([point(i, j), point(i + 1, j + 1), point(i, j + 1)]);
+        }
+    }
+    let sphere = TriangleMesh { triangles };
+    let reference = ReferenceGeometry { area: PI, length: 1.0, moment_point: [0.0; 3] };
+    let sweep = NewtonianAerodynamics::sweep(&sphere, 8.0, &[0.0, 0.2, 0.4], NewtonianMethod::ModifiedNewtonian, reference, 1.4).expect("newtonian sweep failed");
+    let max_pressure_coefficient = newtonian::calc_max_pressure_coefficient(8.0, 1.4).unwrap();
+    assert!((max_pressure_coefficient - 1.83).abs() < 0.01);
+    for aerodynamics in &sweep {
+        assert!((aerodynamics.drag_coefficient / (max_pressure_coefficient / 2.0) - 1.0).abs() < 2e-3);
+        assert!(aerodynamics.lift_coefficient.abs() < 1e-3 && aerodynamics.pitching_moment_coefficient.abs() < 1e-4);
+    }
+    let newtonian = NewtonianAerodynamics::new(&sphere, 8.0, 0.0, NewtonianMethod::Newtonian, reference, 1.4).expect("newtonian failed");
+    assert!((newtonian.drag_coefficient - 1.0).abs() < 2e-3);
+}
+
+#[test]
+fn test_supersonic_cone_from_cone_angle() {
+    // mach 2 and a 10 degree cone, the shock sits at 31.2 degrees
+    match taylormaccoll::SupersonicCone::from_mach_and_cone_angle(2.0, 10.0_f64.to_radians(), 1.4) {
+        Ok(cone) => {
+            assert!((cone.shock_angle.to_degrees() - 31.2).abs() < 0.1);
+            assert!((cone.cone_angle.to_degrees() - 10.0).abs() < 1e-3);
+            let round_trip = taylormaccoll::SupersonicCone::from_mach_and_shock_angle(2.0, cone.shock_angle, 1.4).unwrap();
+            assert!((round_trip.surface_pressure_ratio - cone.surface_pressure_ratio).abs() < 1e-6);
+            assert!(cone.surface_temperature_ratio > 1.0 && cone.surface_mach < 2.0);
+        }
+        Err(e) => {
+            panic!("cone from cone angle failed: {:?}", e);
+        }
+    }
+
+    // the largest cone at mach 2 is 40.70 degrees with the shock near 69.2, between
+    // two half degree steps that both give a smaller cone
+    match taylormaccoll::SupersonicCone::from_mach_and_cone_angle(2.0, 40.70_f64.to_radians(), 1.4) {
+        Ok(cone) => {
+            println!("shock angle {:.4}", cone.shock_angle.to_degrees());
+            assert!((cone.cone_angle.to_degrees() - 40.70).abs() < 1e-4);
+            assert!((cone.shock_angle.to_degrees() - 69.2).abs() < 0.5);
+        }
+        Err(e) => {
+            panic!("cone from cone angle failed: {:?}", e);
+        }
+    }
+
+    // no attached shock on a 45 degree cone at mach 2, while a bad gas is not detachment
+    assert!(matches!(taylormaccoll::SupersonicCone::from_mach_and_cone_angle(2.0, PI / 4.0, 1.4), Err(CompressibleFlowError::DetachedShock)));
+    assert!(matches!(taylormaccoll::SupersonicCone::from_mach_and_cone_angle(2.0, 10.0_f64.to_radians(), 1.0), Err(CompressibleFlowError::Flow(IsentropicFlowError::InvalidSpecificHeatRatio))));
+}
+
+#[test]
+fn test_tangent_method() {
+    // tangent wedge on a diamond airfoil is close to shock expansion theory
+    let diamond = [(1.0, 0.0), (0.5, 0.05), (0.0, 0.0), (0.5, -0.05), (1.0, 0.0)];
+    match TangentMethodAirfoil::new(&diamond, 5.0, 0.05, TangentMethod::TangentWedge, 1.4) {
+        Ok(airfoil) => {
+            let exact = ShockExpansionAirfoil::new(&diamond, 5.0, 0.05, 1.4).unwrap();
+            assert!((airfoil.lift_coefficient / exact.lift_coefficient - 1.0).abs() < 0.01);
+            assert!((airfoil.wave_drag_coefficient / exact.wave_drag_coefficient - 1.0).abs() < 0.01);
+        }
+        Err(e) => {
+            panic!("tangent wedge airfoil failed: {:?}", e);
+        }
+    }
+
+    // a cone cylinder boattail, the cone panel reproduces the taylor maccoll
+    // surface pressure, the cylinder sees freestream and the boattail expands
+    let slope = 10.0_f64.to_radians().tan();
+    let profile = [(0.0, 0.0), (1.0, slope), (2.0, slope), (2.5, slope - 0.05)];
+    let body = TangentMethodBody::new(&profile, 6.0, TangentMethod::TangentCone, 1.4).expect("tangent cone body failed");
+    let cone = taylormaccoll::SupersonicCone::from_mach_and_cone_angle(6.0, 10.0_f64.to_radians(), 1.4).unwrap();
+    assert!((body.panels[0].pressure_ratio - cone.surface_pressure_ratio).abs() < 1e-9);
+    assert!((body.panels[1].pressure_ratio - 1.0).abs() < 1e-12);
+    assert!(body.panels[2].pressure_ratio < 1.0 && body.panels[2].mach_number.is_some_and(|mach_number| mach_number > 6.0));
+    let wedge = tangentmethod::calc_pressure_ratio(10.0_f64.to_radians(), 6.0, TangentMethod::TangentWedge, 1.4).unwrap();
+    assert!(wedge.1 > cone.surface_pressure_ratio);
+    assert!(body.wave_drag_coefficient > 0.0);
+    assert!(body.panels.iter().all(|panel| !panel.detached));
+
+    // a blunt nose detaches the wedge shock, only that panel falls back to newtonian
+    let blunt = TangentMethodBody::new(&[(0.0, 0.0), (0.1, 0.5), (1.0, 0.6)], 3.0, TangentMethod::TangentWedge, 1.4).expect("tangent wedge body failed");
+    assert!(blunt.panels[0].detached && !blunt.panels[1].detached);
+}
@@ -0,0 +1,132 @@
+use crate::isentropic::{self, valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::shockexpansion::{calc_force_coefficients, split_surface, AirfoilPanel, ShockExpansionAirfoil};
+
+
+#[derive(Debug, Clone, Copy)]
+pub enum ThinAirfoilTheory {
+    Ackeret,  // first order, Cp = C1 δ
+    Busemann, // second order, Cp = C1 δ + C2 δ²
+}
+
+#[derive(Debug)]
+pub struct ThinAirfoil {
+    // same surface convention and reference lengths as ShockExpansionAirfoil
+    pub upper_panels: Vec<AirfoilPanel>,
+    pub lower_panels: Vec<AirfoilPanel>,
+    pub lift_coefficient: f64,
+    pub wave_drag_coefficient: f64,
+    pub moment_coefficient: f64,
+}
+
+#[derive(Debug)]
+pub struct ThinAirfoilComparison {
+    // linear theory minus shock expansion theory for the same geometry
+    pub lift_coefficient_error: f64,
+    pub wave_drag_coefficient_error: f64,
+    pub moment_coefficient_error: f64,
+    pub max_pressure_coefficient_error: f64, // largest |ΔCp| over the panels
+}
+
+impl ThinAirfoil {
+    pub fn new(surface: &[(f64, f64)], freestream_mach: f64, angle_of_attack: f64, theory: ThinAirfoilTheory, specific_heat_ratio: f64) -> Result<ThinAirfoil, CompressibleFlowError> {
+        // each panel takes the pressure of its local deflection from the
+        // freestream, no waves interact so the panels are independent
+        if !valid_specific_heat_ratio(specific_heat_ratio) {
+            return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+        }
+        if freestream_mach <= 1.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        let (upper_surface, lower_surface) = split_surface(surface)?;
+
+        let panel = |pair: &[(f64, f64)], deflection_angle: f64| -> Result<AirfoilPanel, CompressibleFlowError> {
+            let pressure_coefficient: f64 = match theory {
+                ThinAirfoilTheory::Ackeret => calc_ackeret_pressure_coefficient(deflection_angle, freestream_mach)?,
+                ThinAirfoilTheory::Busemann => calc_busemann_pressure_coefficient(deflection_angle, freestream_mach, specific_heat_ratio)?,
+            };
+            let pressure_ratio: f64 = 1.0 + specific_heat_ratio * freestream_mach.powi(2) / 2.0 * pressure_coefficient;
+            // local mach number from p / p0 with the freestream stagnation pressure,
+            // none once linear theory puts the pressure outside (0, p0]
+            let isentropic_pressure_ratio: f64 = pressure_ratio * isentropic::calc_pressure_ratio_from_mach(freestream_mach, specific_heat_ratio)?;
+            let mach_number: Option<f64> = if isentropic_pressure_ratio > 0.0 && isentropic_pressure_ratio <= 1.0 {
+                Some(isentropic::calc_mach_from_pressure_ratio(isentropic_pressure_ratio, specific_heat_ratio)?)
+            } else {
+                None
+            };
+            Ok(AirfoilPanel {
+                start: pair[0],
+                end: pair[1],
+                mach_number,
+                pressure_ratio,
+                pressure_coefficient,
+                detached: false,
+            })
+        };
+        let panel_angle = |pair: &[(f64, f64)]| (pair[1].1 - pair[0].1).atan2(pair[1].0 - pair[0].0);
+
+        // compression is positive, as in shock expansion theory
+        let upper_panels: Vec<AirfoilPanel> = upper_surface.windows(2)
+            .map(|pair| panel(pair, panel_angle(pair) - angle_of_attack))
+            .collect::<Result<_, _>>()?;
+        let lower_panels: Vec<AirfoilPanel> = lower_surface.windows(2)
+            .map(|pair| panel(pair, angle_of_attack - panel_angle(pair)))
+            .collect::<Result<_, _>>()?;
+
+        let chord: f64 = surface[0].0 - upper_surface[0].0;
+        let reference: (f64, f64) = (upper_surface[0].0 + 0.25 * chord, upper_surface[0].1);
+        let (lift_coefficient, wave_drag_coefficient, moment_coefficient) =
+            calc_force_coefficients(&upper_panels, &lower_panels, angle_of_attack, chord, reference);
+
+        Ok(ThinAirfoil {
+            upper_panels,
+            lower_panels,
+            lift_coefficient,
+            wave_drag_coefficient,
+            moment_coefficient,
+        })
+    }
+
+    pub fn compare(&self, exact: &ShockExpansionAirfoil) -> Result<ThinAirfoilComparison, CompressibleFlowError> {
+        // both solutions have to be on the same surface
+        if self.upper_panels.len() != exact.upper_panels.len() || self.lower_panels.len() != exact.lower_panels.len() {
+            return Err(CompressibleFlowError::InvalidGeometry);
+        }
+        let max_pressure_coefficient_error: f64 = self.upper_panels.iter().chain(self.lower_panels.iter())
+            .zip(exact.upper_panels.iter().chain(exact.lower_panels.iter()))
+            .map(|(linear, exact)| (linear.pressure_coefficient - exact.pressure_coefficient).abs())
+            .fold(0.0, f64::max);
+
+        Ok(ThinAirfoilComparison {
+            lift_coefficient_error: self.lift_coefficient - exact.lift_coefficient,
+            wave_drag_coefficient_error: self.wave_drag_coefficient - exact.wave_drag_coefficient,
+            moment_coefficient_error: self.moment_coefficient - exact.moment_coefficient,
+            max_pressure_coefficient_error,
+        })
+    }
+}
+
+pub fn compare_with_shock_expansion(surface: &[(f64, f64)], freestream_mach: f64, angle_of_attack: f64, theory: ThinAirfoilTheory, specific_heat_ratio: f64) -> Result<ThinAirfoilComparison, CompressibleFlowError> {
+    let linear: ThinAirfoil = ThinAirfoil::new(surface, freestream_mach, angle_of_attack, theory, specific_heat_ratio)?;
+    let exact: ShockExpansionAirfoil = ShockExpansionAirfoil::new(surface, freestream_mach, angle_of_attack, specific_heat_ratio)?;
+    linear.compare(&exact)
+}
+
+pub fn calc_ackeret_pressure_coefficient(deflection_angle: f64, mach_number: f64) -> Result<f64, CompressibleFlowError> {
+    // Cp = 2 δ / √(M² - 1), δ positive for compression
+    if mach_number <= 1.0 {
+        return Err(IsentropicFlowError::InvalidMachNumber.into());
+    }
+    Ok(2.0 * deflection_angle / (mach_number.powi(2) - 1.0).sqrt())
+}
+
+pub fn calc_busemann_pressure_coefficient(deflection_angle: f64, mach_number: f64, specific_heat_ratio: f64) -> Result<f64, CompressibleFlowError> {
+    // Cp = C1 δ + C2 δ², C2 = ((γ + 1) M⁴ - 4 (M² - 1)) / (2 (M² - 1)²)
+    if !valid_specific_heat_ratio(specific_heat_ratio) {
+        return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+    }
+    let first_order: f64 = calc_ackeret_pressure_coefficient(deflection_angle, mach_number)?;
+    let beta_squared: f64 = mach_number.powi(2) - 1.0;
+    let second_order_coefficient: f64 = ((specific_heat_ratio + 1.0) * mach_number.powi(4) - 4.0 * beta_squared) / (2.0 * beta_squared.powi(2));
+    Ok(first_order + second_order_coefficient * deflection_angle.powi(2))
+}
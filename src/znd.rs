@@ -0,0 +1,101 @@
+use crate::isentropic::{valid_specific_heat_ratio, IsentropicFlowError};
+use crate::error::CompressibleFlowError;
+use crate::detonation::{self, DetonationBranch, DetonationState};
+use crate::numerics::dormand_prince;
+
+
+#[derive(Debug)]
+pub struct ZndDetonation {
+    // steady reaction zone behind the leading shock in the wave frame, states
+    // are scaled by the upstream state, velocities by a1 and distances by a1 / k
+    // where k is the pre-exponential factor of the rate
+    pub wave_mach: f64,                      // D / a1
+    pub von_neumann_state: DetonationState,  // just behind the shock, λ = 0
+    pub distance: Vec<f64>,                  // x behind the shock
+    pub reaction_progress: Vec<f64>,         // λ, 0 unburnt to 1 burnt
+    pub pressure_ratio: Vec<f64>,            // p / p1
+    pub temperature_ratio: Vec<f64>,         // T / T1
+    pub density_ratio: Vec<f64>,             // ρ / ρ1
+    pub velocity: Vec<f64>,                  // u / a1 relative to the shock
+    pub induction_length: f64,               // distance to the peak heat release rate
+}
+
+impl ZndDetonation {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        overdrive: f64,          // (D / DCJ)², 1 for a chapman jouguet detonation
+        heat_release: f64,       // q / (R T1)
+        activation_energy: f64,  // Ea / (R T1)
+        length: f64,             // distance to integrate behind the shock
+        specific_heat_ratio: f64,
+        tolerance: Option<f64>,
+        max_step: Option<f64>,
+    ) -> Result<ZndDetonation, CompressibleFlowError> {
+        // one step irreversible reaction dλ/dt = (1 - λ) exp(-Ea / (R T)), every
+        // state lies on the rayleigh line and the partial hugoniot with heat
+        // release λ q, so only λ has to be integrated, dλ/dx = (dλ/dt) / u
+        if !valid_specific_heat_ratio(specific_heat_ratio) {
+            return Err(IsentropicFlowError::InvalidSpecificHeatRatio.into());
+        }
+        if overdrive < 1.0 {
+            return Err(IsentropicFlowError::InvalidMachNumber.into());
+        }
+        if activation_energy < 0.0 {
+            return Err(IsentropicFlowError::InvalidTemperatureRatio.into());
+        }
+        if length <= 0.0 {
+            return Err(CompressibleFlowError::InvalidGeometry);
+        }
+        let wave_mach: f64 = detonation::calc_chapman_jouguet_mach(heat_release, specific_heat_ratio, true)? * overdrive.sqrt();
+        let von_neumann_state: DetonationState = detonation::calc_von_neumann_state(wave_mach, specific_heat_ratio)?;
+
+        // the heat release that makes the wave its cj speed ends the steady zone
+        // at the sonic point, rounding at the cj speed can put λ q a hair past it
+        // where the rayleigh line misses the hugoniot, so the release is held there
+        let sonic_heat_release: f64 = 2.0 * specific_heat_ratio / (specific_heat_ratio.powi(2) - 1.0)
+            * ((wave_mach.powi(2) - 1.0) / (2.0 * wave_mach)).powi(2);
+        let state = |reaction_progress: f64| {
+            let released: f64 = (heat_release * reaction_progress.clamp(0.0, 1.0)).min(sonic_heat_release);
+            detonation::calc_state(wave_mach, released, DetonationBranch::Strong, specific_heat_ratio)
+        };
+        let reaction_rate = |reaction_progress: f64, state: &DetonationState| {
+            // dλ/dx
+            (1.0 - reaction_progress.clamp(0.0, 1.0)) * (-activation_energy / state.temperature_ratio).exp() / (wave_mach * state.velocity_ratio)
+        };
+        let f = |_x: f64, y: &[f64]| -> Vec<f64> {
+            match state(y[0]) {
+                Ok(current) => vec![reaction_rate(y[0], &current)],
+                Err(_) => vec![f64::NAN],
+            }
+        };
+        let (distance, solution) = dormand_prince(&f, 0.0, &[0.0], length, tolerance, Some(max_step.unwrap_or(length / 500.0)))?;
+
+        let reaction_progress: Vec<f64> = solution.iter().map(|y| y[0].clamp(0.0, 1.0)).collect();
+        let states: Vec<DetonationState> = reaction_progress.iter().map(|&progress| state(progress)).collect::<Result<_, _>>()?;
+        let rates: Vec<f64> = reaction_progress.iter().zip(states.iter()).map(|(&progress, current)| reaction_rate(progress, current)).collect();
+
+        // peak of dλ/dx, refined with a parabola through the neighbouring steps
+        let peak: usize = (0..rates.len()).max_by(|&a, &b| rates[a].total_cmp(&rates[b])).ok_or(IsentropicFlowError::WhatTheFuck)?;
+        let induction_length: f64 = if peak == 0 || peak == rates.len() - 1 {
+            distance[peak]
+        } else {
+            let (x0, x1, x2) = (distance[peak - 1], distance[peak], distance[peak + 1]);
+            let (r0, r1, r2) = (rates[peak - 1], rates[peak], rates[peak + 1]);
+            let numerator: f64 = (x1 - x0).powi(2) * (r1 - r2) - (x1 - x2).powi(2) * (r1 - r0);
+            let denominator: f64 = (x1 - x0) * (r1 - r2) - (x1 - x2) * (r1 - r0);
+            if denominator.abs() > 0.0 { x1 - 0.5 * numerator / denominator } else { x1 }
+        };
+
+        Ok(ZndDetonation {
+            wave_mach,
+            von_neumann_state,
+            distance,
+            reaction_progress,
+            pressure_ratio: states.iter().map(|current| current.pressure_ratio).collect(),
+            temperature_ratio: states.iter().map(|current| current.temperature_ratio).collect(),
+            density_ratio: states.iter().map(|current| current.density_ratio).collect(),
+            velocity: states.iter().map(|current| wave_mach * current.velocity_ratio).collect(),
+            induction_length,
+        })
+    }
+}